@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_EPSILON: f64 = 1e-9;
+
+pub fn approx_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    (a - b).abs() <= epsilon * a.abs().max(b.abs()).max(1.0)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub name: String,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64, name: &str) -> Point {
+        Point { x, y, name: name.to_string() }
+    }
+
+    pub fn distance(&self, other: &Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+
+    pub fn approx_eq(&self, other: &Point, epsilon: f64) -> bool {
+        approx_eq(self.x, other.x, epsilon) && approx_eq(self.y, other.y, epsilon)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Size {
+    #[serde(rename = "w")]
+    pub width: f64,
+    #[serde(rename = "h")]
+    pub height: f64,
+}
+
+impl Size {
+    pub fn new(width: f64, height: f64) -> Size {
+        Size { width, height }
+    }
+
+    pub fn scale(&mut self, factor: f64) {
+        self.width *= factor;
+        self.height *= factor;
+    }
+}
+
+impl Dimensions for Size {
+    fn width(&self) -> f64 {
+        self.width
+    }
+
+    fn height(&self) -> f64 {
+        self.height
+    }
+}
+
+pub trait Dimensions {
+    fn width(&self) -> f64;
+    fn height(&self) -> f64;
+
+    fn area(&self) -> f64 {
+        self.width().abs() * self.height().abs()
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (self.width().abs() + self.height().abs())
+    }
+
+    fn diagonal(&self) -> f64 {
+        self.width().hypot(self.height())
+    }
+
+    fn smaller_side(&self) -> f64 {
+        self.width().abs().min(self.height().abs())
+    }
+
+    fn bigger_side(&self) -> f64 {
+        self.width().abs().max(self.height().abs())
+    }
+}
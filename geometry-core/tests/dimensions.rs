@@ -0,0 +1,31 @@
+use geometry_core::{approx_eq, Dimensions, Point, Size, DEFAULT_EPSILON};
+
+#[test]
+fn dimensions_are_derived_from_the_sides() {
+    let mut size = Size::new(6.0, -8.0);
+    assert_eq!(size.area(), 48.0);
+    assert_eq!(size.perimeter(), 28.0);
+    assert_eq!(size.diagonal(), 10.0);
+    assert_eq!((size.smaller_side(), size.bigger_side()), (6.0, 8.0));
+    size.scale(0.5);
+    assert_eq!(size, Size::new(3.0, -4.0));
+    assert_eq!(serde_json::to_string(&size).unwrap(), r#"{"w":3.0,"h":-4.0}"#);
+}
+
+#[test]
+fn points_measure_distances() {
+    let a = Point::new(-5.0, -3.0, "A");
+    let c = Point::new(15.0, 37.5, "C");
+    assert_eq!(Point::new(0.0, 0.0, "O").distance(&Point::new(3.0, 4.0, "P")), 5.0);
+    assert!(approx_eq(a.distance(&c), 2040.25f64.sqrt(), DEFAULT_EPSILON));
+    assert_eq!(serde_json::to_string(&c).unwrap(), r#"{"x":15.0,"y":37.5,"name":"C"}"#);
+}
+
+#[test]
+fn comparisons_respect_the_epsilon() {
+    assert!(approx_eq(0.1 + 0.2, 0.3, DEFAULT_EPSILON));
+    assert!(!approx_eq(0.1 + 0.2, 0.3, 0.0));
+    assert!(approx_eq(1e12, 1e12 + 1.0, DEFAULT_EPSILON));
+    assert!(!approx_eq(1.0, 1.001, 1e-6));
+    assert!(Point::new(1.0, 2.0, "A").approx_eq(&Point::new(1.0005, 2.0, "B"), 1e-3));
+}
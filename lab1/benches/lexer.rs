@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use lab1::{lex, relex, DfaLexer, TextEdit};
+
+const SNIPPET: &str = r#"fn shvachka_{n}(a: f64, hvac: i32) {
+    let mut sk_{n} = sqrt(a) * 2.5 + -{n};
+    if sk_{n} >= 10 && hvac != 0 {
+        sk_{n} += cos(a) / 3;
+    } else {
+        sk_{n} -= 1;
+    }
+    let s = "рядок {n}";
+    let c = 'x';
+}
+"#;
+
+fn generate_source(size: usize) -> String {
+    let mut source = String::with_capacity(size + SNIPPET.len());
+    let mut n = 0;
+    while source.len() < size {
+        source.push_str(&SNIPPET.replace("{n}", &n.to_string()));
+        n += 1;
+    }
+    source
+}
+
+fn bench_lex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lex");
+    group.sample_size(20);
+    for size in [20_000, 200_000, 2_000_000] {
+        let source = generate_source(size);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &source, |b, source| {
+            b.iter(|| lex(source))
+        });
+    }
+    group.finish();
+}
+
+fn bench_relex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("relex");
+    group.sample_size(20);
+    for size in [20_000, 200_000, 2_000_000] {
+        let source = generate_source(size);
+        let previous = lex(&source);
+        let offset = source[..source.len() / 2].rfind("sk_").unwrap();
+        let edit = TextEdit::new(offset..offset + 2, "hvac");
+        let edited = edit.apply(&source).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(size), &edited, |b, edited| {
+            b.iter_batched(|| previous.clone(), |previous| relex(edited, previous, &edit), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let dfa = DfaLexer::new().unwrap();
+    let mut group = c.benchmark_group("dispatch");
+    group.sample_size(20);
+    for size in [20_000, 200_000, 2_000_000] {
+        let source = generate_source(size);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::new("lex", size), &source, |b, source| b.iter(|| lex(source)));
+        group.bench_with_input(BenchmarkId::new("dfa", size), &source, |b, source| b.iter(|| dfa.tokenize(source)));
+    }
+    group.finish();
+}
+
+fn bench_dfa_compile(c: &mut Criterion) {
+    c.bench_function("dfa_compile", |b| b.iter(|| DfaLexer::new().unwrap()));
+}
+
+criterion_group!(benches, bench_lex, bench_relex, bench_dispatch, bench_dfa_compile);
+criterion_main!(benches);
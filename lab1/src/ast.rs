@@ -0,0 +1,345 @@
+use std::fmt;
+use crate::token::{LiteralValue, Span, Token};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub attributes: Vec<Attribute>,
+    pub items: Vec<Item>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub inner: bool,
+    pub path: Path,
+    pub args: Vec<Token>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub attributes: Vec<Attribute>,
+    pub kind: ItemKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    Function(Function),
+    Struct(Struct),
+    Enum(Enum),
+    Impl(Impl),
+    Use(Use),
+    Macro(MacroDef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub params: Vec<Param>,
+    pub return_type: Option<Type>,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub mutable: bool,
+    pub name: Ident,
+    pub ty: Option<Type>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: Ident,
+    pub fields: Fields,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fields {
+    Unit,
+    Tuple(Vec<Type>),
+    Named(Vec<Field>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: Ident,
+    pub ty: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impl {
+    pub trait_name: Option<Path>,
+    pub self_type: Type,
+    pub functions: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Use {
+    pub path: Path,
+    pub glob: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroDef {
+    pub name: Ident,
+    pub params: Vec<Token>,
+    pub body: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    pub segments: Vec<Ident>,
+    pub span: Span,
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.segments.iter().map(|s| s.name.as_str()).collect();
+        write!(f, "{}", names.join("::"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    pub kind: TypeKind,
+    pub span: Span,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Path { path, generics } if generics.is_empty() => write!(f, "{}", path),
+            TypeKind::Path { path, generics } => {
+                let generics: Vec<String> = generics.iter().map(Type::to_string).collect();
+                write!(f, "{}<{}>", path, generics.join(", "))
+            }
+            TypeKind::Reference { lifetime, mutable, inner } => {
+                write!(f, "&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime.name)?;
+                }
+                if *mutable {
+                    write!(f, "mut ")?;
+                }
+                write!(f, "{}", inner)
+            }
+            TypeKind::Array { element, length: None } => write!(f, "[{}]", element),
+            TypeKind::Array { element, length: Some(length) } => match &length.kind {
+                ExprKind::Literal(LiteralValue::Integer { value, negative, .. }) => write!(f, "[{}; {}{}]", element, if *negative { "-" } else { "" }, value),
+                _ => write!(f, "[{}; _]", element),
+            },
+            TypeKind::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(Type::to_string).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    Path { path: Path, generics: Vec<Type> },
+    Reference { lifetime: Option<Ident>, mutable: bool, inner: Box<Type> },
+    Array { element: Box<Type>, length: Option<Box<Expr>> },
+    Tuple(Vec<Type>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub statements: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    Let { mutable: bool, name: Ident, ty: Option<Type>, init: Option<Expr> },
+    Item(Item),
+    Expr(Expr),
+    Semi(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Literal(LiteralValue),
+    Bool(bool),
+    Path(Path),
+    Unary { op: UnaryOp, operand: Box<Expr> },
+    Binary { op: BinaryOp, lhs: Box<Expr>, rhs: Box<Expr> },
+    Assign { op: Option<BinaryOp>, target: Box<Expr>, value: Box<Expr> },
+    Range { start: Option<Box<Expr>>, end: Option<Box<Expr>>, inclusive: bool },
+    Call { callee: Box<Expr>, args: Vec<Expr> },
+    BuiltinCall { function: Ident, args: Vec<Expr> },
+    MethodCall { receiver: Box<Expr>, method: Ident, args: Vec<Expr> },
+    Field { base: Box<Expr>, field: Ident },
+    Index { base: Box<Expr>, index: Box<Expr> },
+    Try(Box<Expr>),
+    StructLiteral { path: Path, fields: Vec<(Ident, Expr)> },
+    Array(Vec<Expr>),
+    Tuple(Vec<Expr>),
+    Paren(Box<Expr>),
+    Block(Block),
+    If { condition: Box<Expr>, then_branch: Block, else_branch: Option<Box<Expr>> },
+    While { label: Option<Ident>, condition: Box<Expr>, body: Block },
+    Loop { label: Option<Ident>, body: Block },
+    For { label: Option<Ident>, binding: Ident, iterable: Box<Expr>, body: Block },
+    Break { label: Option<Ident>, value: Option<Box<Expr>> },
+    Continue { label: Option<Ident> },
+    Return(Option<Box<Expr>>),
+    MacroCall { name: Ident, args: Vec<Expr> },
+    Error,
+}
+
+impl ExprKind {
+    pub fn is_block_like(&self) -> bool {
+        matches!(
+            self,
+            ExprKind::Block(_) | ExprKind::If { .. } | ExprKind::While { .. } | ExprKind::Loop { .. } | ExprKind::For { .. }
+        )
+    }
+
+    pub fn is_place(&self) -> bool {
+        match self {
+            ExprKind::Path(_) | ExprKind::Field { .. } | ExprKind::Index { .. } | ExprKind::Error => true,
+            ExprKind::Unary { op, .. } => *op == UnaryOp::Deref,
+            ExprKind::Paren(inner) => inner.kind.is_place(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+    Deref,
+    Ref,
+    RefMut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitXor,
+    BitOr,
+    BitAnd,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn from_symbol(symbol: &str) -> Option<BinaryOp> {
+        let op = match symbol {
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Sub,
+            "*" => BinaryOp::Mul,
+            "/" => BinaryOp::Div,
+            "%" => BinaryOp::Rem,
+            "^" => BinaryOp::BitXor,
+            "|" => BinaryOp::BitOr,
+            "&" => BinaryOp::BitAnd,
+            "<<" => BinaryOp::Shl,
+            ">>" => BinaryOp::Shr,
+            "==" => BinaryOp::Eq,
+            "!=" => BinaryOp::Ne,
+            "<" => BinaryOp::Lt,
+            "<=" => BinaryOp::Le,
+            ">" => BinaryOp::Gt,
+            ">=" => BinaryOp::Ge,
+            "&&" => BinaryOp::And,
+            "||" => BinaryOp::Or,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::BitXor => "^",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => 3,
+            BinaryOp::BitOr => 4,
+            BinaryOp::BitXor => 5,
+            BinaryOp::BitAnd => 6,
+            BinaryOp::Shl | BinaryOp::Shr => 7,
+            BinaryOp::Add | BinaryOp::Sub => 8,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 9,
+        }
+    }
+
+    pub fn is_comparison(self) -> bool {
+        self.precedence() == 3
+    }
+}
@@ -0,0 +1,143 @@
+use lab1::lsp::{diagnostics, document_symbols, semantic_token_legend, semantic_tokens};
+use lab1::{lex_with_options, parse, resolve, IdentifierMode, LexerOptions};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{DocumentSymbolRequest, Request as _, SemanticTokensFullRequest};
+use lsp_types::{
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbolParams,
+    DocumentSymbolResponse, InitializeParams, OneOf, PublishDiagnosticsParams, SemanticTokens, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+use std::collections::HashMap;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Sync + Send>>;
+
+struct Server {
+    connection: Connection,
+    options: LexerOptions,
+    documents: HashMap<String, String>,
+}
+
+impl Server {
+    fn publish_diagnostics(&self, uri: Uri, version: Option<i32>) -> Result<()> {
+        let diagnostics = match self.documents.get(uri.as_str()) {
+            Some(text) => {
+                let lexed = lex_with_options(text, self.options);
+                let parsed = parse(&lexed.tokens);
+                let resolved = resolve(&parsed.program);
+                diagnostics(text, &lexed, &parsed, &resolved)
+            }
+            None => Vec::new(),
+        };
+        let params = PublishDiagnosticsParams { uri, diagnostics, version };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        let handled = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => serde_json::from_value(notification.params).map(|params: DidOpenTextDocumentParams| {
+                let document = params.text_document;
+                self.documents.insert(document.uri.as_str().to_string(), document.text);
+                self.publish_diagnostics(document.uri, Some(document.version))
+            }),
+            DidChangeTextDocument::METHOD => serde_json::from_value(notification.params).map(|params: DidChangeTextDocumentParams| {
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(params.text_document.uri.as_str().to_string(), change.text);
+                }
+                self.publish_diagnostics(params.text_document.uri, Some(params.text_document.version))
+            }),
+            DidCloseTextDocument::METHOD => serde_json::from_value(notification.params).map(|params: DidCloseTextDocumentParams| {
+                self.documents.remove(params.text_document.uri.as_str());
+                self.publish_diagnostics(params.text_document.uri, None)
+            }),
+            _ => Ok(Ok(())),
+        };
+        handled.unwrap_or_else(|error| {
+            eprintln!("lab1-lsp: ignoring `{}` notification with invalid params: {}", notification.method, error);
+            Ok(())
+        })
+    }
+
+    fn handle_request(&self, request: Request) -> Result<()> {
+        let id = request.id.clone();
+        let response = match request.method.as_str() {
+            SemanticTokensFullRequest::METHOD => serde_json::from_value(request.params).map(|params: SemanticTokensParams| {
+                let result = self.documents.get(params.text_document.uri.as_str()).map(|text| {
+                    let data = semantic_tokens(text, &lex_with_options(text, self.options));
+                    SemanticTokensResult::Tokens(SemanticTokens { result_id: None, data })
+                });
+                Response::new_ok(id.clone(), result)
+            }),
+            DocumentSymbolRequest::METHOD => serde_json::from_value(request.params).map(|params: DocumentSymbolParams| {
+                let result = self.documents.get(params.text_document.uri.as_str()).map(|text| {
+                    let lexed = lex_with_options(text, self.options);
+                    let resolved = resolve(&parse(&lexed.tokens).program);
+                    DocumentSymbolResponse::Nested(document_symbols(text, &resolved))
+                });
+                Response::new_ok(id.clone(), result)
+            }),
+            method => Ok(Response::new_err(id.clone(), ErrorCode::MethodNotFound as i32, format!("unsupported request `{}`", method))),
+        };
+        let response = response.unwrap_or_else(|error| {
+            Response::new_err(id, ErrorCode::InvalidParams as i32, format!("invalid params for `{}`: {}", request.method, error))
+        });
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+            legend: semantic_token_legend(),
+            full: Some(SemanticTokensFullOptions::Bool(true)),
+            ..SemanticTokensOptions::default()
+        })),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    let params: InitializeParams = serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?).unwrap_or_else(|error| {
+        eprintln!("lab1-lsp: ignoring invalid initialize params: {}", error);
+        InitializeParams::default()
+    });
+    let unicode = params
+        .initialization_options
+        .and_then(|options| options.get("unicode")?.as_bool())
+        .unwrap_or(false);
+    let identifiers = if unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+
+    let mut server = Server {
+        connection,
+        options: LexerOptions { keep_comments: true, identifiers, ..LexerOptions::default() },
+        documents: HashMap::new(),
+    };
+    server.run()?;
+    drop(server);
+    io_threads.join()?;
+    Ok(())
+}
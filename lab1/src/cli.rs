@@ -0,0 +1,294 @@
+use clap::{Parser, ValueEnum};
+use crate::{
+    format_source_with_lexicon, interpret_function, lex_with_lexicon, parse, render_ansi, render_diagnostic, render_diagnostic_location, render_html, resolve, source_stats, DfaLexer, IdentifierMode,
+    LexError, LexOutput, LexerConfig, LexerOptions, Lexicon, SourceStats, StreamLexer, SymbolTable, Token, TokenSpec, TokenType,
+};
+use serde_json::json;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+    Symbols,
+    Ast,
+    Html,
+    Ansi,
+    Fmt,
+    Run,
+    Stats,
+    StatsJson,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "lab1", about = "Lexical analyser and parser for the lab1 language")]
+pub struct Cli {
+    /// Source files to lex; reads stdin when empty or `-`
+    paths: Vec<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Lexer profile (.toml or .json) overriding keywords, functions, types and alphabet
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    #[arg(long)]
+    keep_comments: bool,
+
+    /// Also emit whitespace and newline tokens so the token texts rebuild the input exactly
+    #[arg(long)]
+    trivia: bool,
+
+    /// Accept any Unicode XID identifier (NFC-normalised) instead of the configured alphabet; alphabet violations are not reported
+    #[arg(long)]
+    unicode: bool,
+
+    /// Lex in chunks without loading whole files into memory (table and json formats only)
+    #[arg(long)]
+    stream: bool,
+
+    /// Lex with a minimised DFA generated from the lexer profile instead of the hand-written lexer
+    #[arg(long)]
+    dfa: bool,
+
+    /// Lex with a DFA compiled from a declarative token spec (.toml or .json)
+    #[arg(long)]
+    spec: Option<PathBuf>,
+
+    /// Print the token spec generated from the lexer profile as TOML and exit
+    #[arg(long)]
+    dump_spec: bool,
+
+    /// Function to call first with `--format run`
+    #[arg(long, default_value = "main")]
+    entry: String,
+}
+
+fn read_source(path: &PathBuf) -> Result<(String, String), Box<dyn Error>> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(("<stdin>".to_string(), input));
+    }
+    let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok((path.display().to_string(), input))
+}
+
+fn print_table_header(name: &str) {
+    println!("{}", name);
+    println!("{:<10} {:<14} Text", "Line:Col", "Kind");
+}
+
+fn print_table_row(token: &Token) {
+    let location = format!("{}:{}", token.span.line, token.span.column);
+    let text = token.content.replace('\n', "\\n").replace('\r', "\\r").replace('\t', "\\t");
+    println!("{:<10} {:<14} {}", location, token.type_name.to_string(), text);
+}
+
+fn print_table(name: &str, output: &LexOutput) {
+    print_table_header(name);
+    for token in &output.tokens {
+        print_table_row(token);
+    }
+    println!();
+}
+
+fn print_json_row(name: &str, token: &Token) {
+    let line = json!({
+        "file": name,
+        "kind": token.type_name,
+        "text": token.content,
+        "span": token.span,
+    });
+    println!("{}", line);
+}
+
+fn print_json(name: &str, output: &LexOutput) {
+    for token in &output.tokens {
+        print_json_row(name, token);
+    }
+}
+
+fn print_symbols(name: &str, table: &SymbolTable) {
+    println!("{}", name);
+    println!("{:<20} {:<10} {:<12} {:<4} {:>5}  {:<10} Uses", "Identifier", "Kind", "Type", "Mut", "Scope", "Declared");
+    for symbol in &table.symbols {
+        let mut usages = symbol.usages.clone();
+        usages.sort_by_key(|s| s.start);
+        let usages: Vec<String> = usages.iter().map(|s| format!("{}:{}", s.line, s.column)).collect();
+        println!(
+            "{:<20} {:<10} {:<12} {:<4} {:>5}  {:<10} {}",
+            symbol.name,
+            format!("{:?}", symbol.kind),
+            symbol.ty.as_deref().unwrap_or("-"),
+            if symbol.mutable { "mut" } else { "" },
+            table.scopes[symbol.scope].depth,
+            format!("{}:{}", symbol.span.line, symbol.span.column),
+            usages.join(" "),
+        );
+    }
+    println!();
+}
+
+fn print_stats(name: &str, stats: &SourceStats) {
+    println!("{}", name);
+    println!("{:<24} {}", "Lines", stats.lines);
+    println!("{:<24} {}", "Tokens", stats.tokens);
+    println!("{:<24} {} ({:.2}%)", "Invalid tokens", stats.invalid, stats.invalid_ratio * 100.0);
+    println!("{:<24} {}", "Max nesting depth", stats.max_depth);
+    println!("{:<24} {}", "Cyclomatic complexity", stats.complexity);
+    println!();
+    println!("{:<24} Count", "Kind");
+    for (kind, count) in &stats.kinds {
+        println!("{:<24} {}", kind.to_string(), count);
+    }
+    println!();
+    println!("{:<24} Uses", "Keyword");
+    for (keyword, count) in &stats.keywords {
+        println!("{:<24} {}", keyword, count);
+    }
+    println!();
+    println!("{:<24} Uses", "Identifier");
+    for (identifier, count) in stats.identifiers_by_frequency() {
+        println!("{:<24} {}", identifier, count);
+    }
+    println!();
+}
+
+fn stream_source(path: &PathBuf, lexicon: &Lexicon, options: LexerOptions, format: OutputFormat) -> Result<bool, Box<dyn Error>> {
+    let (name, reader): (String, Box<dyn Read>) = if path.as_os_str() == "-" {
+        ("<stdin>".to_string(), Box::new(io::stdin().lock()))
+    } else {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        (path.display().to_string(), Box::new(file))
+    };
+
+    if format == OutputFormat::Table {
+        print_table_header(&name);
+    }
+    let mut has_invalid = false;
+    let mut lexer = StreamLexer::with_lexicon(reader, lexicon, options);
+    while let Some(token) = lexer.next() {
+        let token = token.map_err(|e| format!("{}: {}", name, e))?;
+        for error in lexer.take_diagnostics() {
+            eprint!("{}", render_diagnostic_location(&error, &name));
+        }
+        has_invalid |= token.type_name == TokenType::Invalid;
+        match format {
+            OutputFormat::Json => print_json_row(&name, &token),
+            _ => print_table_row(&token),
+        }
+    }
+    if format == OutputFormat::Table {
+        println!();
+    }
+    Ok(has_invalid)
+}
+
+pub fn run(cli: &Cli) -> Result<bool, Box<dyn Error>> {
+    let lexicon = match &cli.config {
+        Some(path) => LexerConfig::load(path)?.compile()?,
+        None => Lexicon::default(),
+    };
+    let identifiers = if cli.unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+    let keep_comments = cli.keep_comments || matches!(cli.format, OutputFormat::Html | OutputFormat::Ansi | OutputFormat::Stats | OutputFormat::StatsJson);
+    let options = LexerOptions { keep_comments, identifiers, trivia: cli.trivia };
+    let paths = if cli.paths.is_empty() { vec![PathBuf::from("-")] } else { cli.paths.clone() };
+    if cli.stream && !matches!(cli.format, OutputFormat::Table | OutputFormat::Json) {
+        return Err("--stream only supports the table and json formats".into());
+    }
+    if cli.dump_spec {
+        print!("{}", TokenSpec::from_lexicon(&lexicon, options)?.to_toml());
+        return Ok(false);
+    }
+    let dfa = match &cli.spec {
+        Some(path) => Some(DfaLexer::compile(&TokenSpec::load(path)?)?),
+        None if cli.dfa => Some(DfaLexer::with_lexicon(&lexicon, options)?),
+        None => None,
+    };
+    if cli.stream && dfa.is_some() {
+        return Err("--stream cannot be combined with --dfa or --spec".into());
+    }
+
+    let mut has_invalid = false;
+    for path in &paths {
+        if cli.stream {
+            has_invalid |= stream_source(path, &lexicon, options, cli.format)?;
+            continue;
+        }
+        let (name, input) = read_source(path)?;
+        let output = match &dfa {
+            Some(dfa) => LexOutput { tokens: dfa.tokenize(&input), ..LexOutput::default() },
+            None => lex_with_lexicon(&input, &lexicon, options),
+        };
+
+        for error in &output.diagnostics {
+            eprintln!("{}", render_diagnostic(error, &input, &name));
+        }
+        has_invalid |= match cli.format {
+            OutputFormat::Run => output.diagnostics.iter().any(|e| !e.is_warning() && !matches!(e, LexError::IllegalIdentifierChar { .. })),
+            _ => output.tokens.iter().any(|t| t.type_name == TokenType::Invalid),
+        };
+
+        match cli.format {
+            OutputFormat::Table => print_table(&name, &output),
+            OutputFormat::Json => print_json(&name, &output),
+            OutputFormat::Symbols => {
+                let parsed = parse(&output.tokens);
+                let resolved = resolve(&parsed.program);
+                for error in &parsed.diagnostics {
+                    eprintln!("{}", render_diagnostic(error, &input, &name));
+                }
+                for error in &resolved.diagnostics {
+                    eprintln!("{}", render_diagnostic(error, &input, &name));
+                }
+                has_invalid |= !parsed.diagnostics.is_empty() || resolved.diagnostics.iter().any(|e| !e.is_warning());
+                print_symbols(&name, &resolved.table);
+            }
+            OutputFormat::Ast => {
+                let parsed = parse(&output.tokens);
+                for error in &parsed.diagnostics {
+                    eprintln!("{}", render_diagnostic(error, &input, &name));
+                }
+                has_invalid |= !parsed.diagnostics.is_empty();
+                println!("{}\n{:#?}", name, parsed.program);
+            }
+            OutputFormat::Html => print!("{}", render_html(&input, &output.tokens, &name)),
+            OutputFormat::Ansi => print!("{}", render_ansi(&input, &output.tokens)),
+            OutputFormat::Fmt => print!("{}", format_source_with_lexicon(&input, &lexicon, options)),
+            OutputFormat::Stats => print_stats(&name, &source_stats(&input, &output.tokens)),
+            OutputFormat::StatsJson => println!("{}", json!({ "file": name, "stats": source_stats(&input, &output.tokens) })),
+            OutputFormat::Run => {
+                let parsed = parse(&output.tokens);
+                for error in &parsed.diagnostics {
+                    eprintln!("{}", render_diagnostic(error, &input, &name));
+                }
+                if !parsed.diagnostics.is_empty() {
+                    has_invalid = true;
+                    continue;
+                }
+                if let Err(error) = interpret_function(&parsed.program, &cli.entry, &mut io::stdout()) {
+                    eprintln!("{}", render_diagnostic(&error, &input, &name));
+                    has_invalid = true;
+                }
+            }
+        }
+    }
+    Ok(has_invalid)
+}
+
+pub fn execute(cli: &Cli) -> ExitCode {
+    match run(cli) {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::lexer::{generate_regex, DATA_TYPES, FUNCTIONS, IDENTIFIER_SYMBOLS, KEY_WORDS};
+
+lazy_static! {
+    pub(crate) static ref DEFAULT_LEXICON: Lexicon = LexerConfig::default().compile().unwrap();
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(String),
+    Parse(String),
+    InvalidPattern(String),
+    UnknownFormat(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(message) => write!(f, "cannot read lexer config: {}", message),
+            ConfigError::Parse(message) => write!(f, "cannot parse lexer config: {}", message),
+            ConfigError::InvalidPattern(message) => write!(f, "invalid identifier pattern: {}", message),
+            ConfigError::UnknownFormat(path) => write!(f, "unknown lexer config format: {} (expected .toml or .json)", path),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LexerConfig {
+    pub key_words: Vec<String>,
+    pub functions: Vec<String>,
+    pub data_types: Vec<String>,
+    pub identifier_symbols: String,
+}
+
+impl Default for LexerConfig {
+    fn default() -> Self {
+        LexerConfig {
+            key_words: KEY_WORDS.iter().map(|s| s.to_string()).collect(),
+            functions: FUNCTIONS.iter().map(|s| s.to_string()).collect(),
+            data_types: DATA_TYPES.iter().map(|s| s.to_string()).collect(),
+            identifier_symbols: IDENTIFIER_SYMBOLS.to_string(),
+        }
+    }
+}
+
+impl LexerConfig {
+    pub fn from_toml(source: &str) -> Result<LexerConfig, ConfigError> {
+        toml::from_str(source).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    pub fn from_json(source: &str) -> Result<LexerConfig, ConfigError> {
+        serde_json::from_str(source).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<LexerConfig, ConfigError> {
+        let source = fs::read_to_string(path).map_err(|e| ConfigError::Io(format!("{}: {}", path.display(), e)))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&source),
+            Some("json") => Self::from_json(&source),
+            _ => Err(ConfigError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    pub fn compile(&self) -> Result<Lexicon, ConfigError> {
+        Ok(Lexicon {
+            config: self.clone(),
+            identifier: Regex::new(&self.identifier_symbols).map_err(|e| ConfigError::InvalidPattern(e.to_string()))?,
+            key_words: generate_regex(&self.key_words),
+            functions: generate_regex(&self.functions),
+            data_types: generate_regex(&self.data_types),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lexicon {
+    config: LexerConfig,
+    pub(crate) identifier: Regex,
+    pub(crate) key_words: Regex,
+    pub(crate) functions: Regex,
+    pub(crate) data_types: Regex,
+}
+
+impl Lexicon {
+    pub fn config(&self) -> &LexerConfig {
+        &self.config
+    }
+
+    pub fn is_data_type(&self, name: &str) -> bool {
+        self.config.data_types.iter().any(|t| t == name)
+    }
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        DEFAULT_LEXICON.clone()
+    }
+}
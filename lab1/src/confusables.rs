@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Other,
+}
+
+const CONFUSABLES: [(char, char); 56] = [
+    ('а', 'a'), ('в', 'B'), ('е', 'e'), ('к', 'k'), ('м', 'M'), ('н', 'H'), ('о', 'o'), ('р', 'p'),
+    ('с', 'c'), ('т', 'T'), ('у', 'y'), ('х', 'x'), ('і', 'i'), ('ј', 'j'), ('ѕ', 's'), ('һ', 'h'),
+    ('ԁ', 'd'), ('ԛ', 'q'), ('ԝ', 'w'), ('ү', 'y'),
+    ('А', 'A'), ('В', 'B'), ('Е', 'E'), ('К', 'K'), ('М', 'M'), ('Н', 'H'), ('О', 'O'), ('Р', 'P'),
+    ('С', 'C'), ('Т', 'T'), ('У', 'Y'), ('Х', 'X'), ('І', 'I'), ('Ј', 'J'), ('Ѕ', 'S'), ('Ԛ', 'Q'),
+    ('Α', 'A'), ('Β', 'B'), ('Ε', 'E'), ('Ζ', 'Z'), ('Η', 'H'), ('Ι', 'I'), ('Κ', 'K'), ('Μ', 'M'),
+    ('Ν', 'N'), ('Ο', 'O'), ('Ρ', 'P'), ('Τ', 'T'), ('Υ', 'Y'), ('Χ', 'X'), ('ο', 'o'), ('ν', 'v'),
+    ('ι', 'i'), ('κ', 'k'), ('ρ', 'p'), ('υ', 'u'),
+];
+
+pub fn script(c: char) -> Script {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Script::Latin,
+        '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+        '\u{0370}'..='\u{03FF}' => Script::Greek,
+        _ => Script::Other,
+    }
+}
+
+pub fn lookalike(c: char) -> Option<char> {
+    CONFUSABLES.iter().find(|(confusable, _)| *confusable == c).map(|(_, latin)| *latin)
+}
+
+pub fn skeleton(identifier: &str) -> String {
+    identifier.chars().map(|c| lookalike(c).unwrap_or(c)).collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Skeletons {
+    first: HashMap<String, String>,
+    limit: Option<usize>,
+}
+
+impl Skeletons {
+    pub fn with_limit(limit: usize) -> Self {
+        Skeletons { first: HashMap::new(), limit: Some(limit) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.first.is_empty()
+    }
+
+    pub fn confusable_with(&mut self, identifier: &str) -> Option<String> {
+        let skeleton = skeleton(identifier);
+        if let Some(other) = self.first.get(&skeleton) {
+            return if other != identifier { Some(other.clone()) } else { None };
+        }
+        if self.limit.is_none_or(|limit| self.first.len() < limit) {
+            self.first.insert(skeleton, identifier.to_string());
+        }
+        None
+    }
+}
+
+pub fn mixed_script_confusables(identifier: &str) -> Vec<(char, char)> {
+    let Some(main_script) = identifier.chars().map(script).find(|s| *s != Script::Other) else {
+        return Vec::new();
+    };
+    identifier
+        .chars()
+        .filter(|c| script(*c) != main_script)
+        .filter_map(|c| lookalike(c).map(|latin| (c, latin)))
+        .chain(
+            identifier
+                .chars()
+                .filter(|c| main_script != Script::Latin && script(*c) == Script::Latin)
+                .filter_map(|c| CONFUSABLES.iter().find(|(_, latin)| *latin == c).map(|(confusable, _)| (c, *confusable))),
+        )
+        .collect()
+}
@@ -0,0 +1,706 @@
+use lazy_static::lazy_static;
+use std::collections::{BTreeMap, HashMap};
+use unicode_xid::UnicodeXID;
+use crate::config::{Lexicon, DEFAULT_LEXICON};
+use crate::lexer::LexerOptions;
+use crate::spec::{SpecError, TokenSpec};
+use crate::token::{Position, Span, Token, TokenType};
+
+const MAX_CONTEXTS: usize = 8;
+const MAX_DEFINITION_DEPTH: usize = 32;
+const LAST_CHAR: u32 = char::MAX as u32;
+
+lazy_static! {
+    static ref WHITESPACE: CharSet = CharSet::from_predicate(char::is_whitespace);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub(crate) struct CharSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CharSet {
+    fn new(mut ranges: Vec<(u32, u32)>) -> CharSet {
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        CharSet { ranges: merged }
+    }
+
+    fn single(c: char) -> CharSet {
+        CharSet { ranges: vec![(c as u32, c as u32)] }
+    }
+
+    pub(crate) fn from_predicate(predicate: impl Fn(char) -> bool) -> CharSet {
+        let mut ranges = Vec::new();
+        let mut start = None;
+        for code in 0..=LAST_CHAR + 1 {
+            let inside = char::from_u32(code).is_some_and(&predicate);
+            match (inside, start) {
+                (true, None) => start = Some(code),
+                (false, Some(lo)) if !(0xD800..=0xDFFF).contains(&code) => {
+                    ranges.push((lo, code - 1));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        CharSet::new(ranges)
+    }
+
+    fn union(&self, other: &CharSet) -> CharSet {
+        CharSet::new(self.ranges.iter().chain(&other.ranges).copied().collect())
+    }
+
+    fn negate(&self) -> CharSet {
+        let mut ranges = Vec::new();
+        let mut next = 0;
+        for &(lo, hi) in &self.ranges {
+            if lo > next {
+                ranges.push((next, lo - 1));
+            }
+            next = hi + 1;
+        }
+        if next <= LAST_CHAR {
+            ranges.push((next, LAST_CHAR));
+        }
+        CharSet { ranges }
+    }
+
+    pub(crate) fn intersect(&self, other: &CharSet) -> CharSet {
+        self.negate().union(&other.negate()).negate()
+    }
+
+    pub(crate) fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        let i = self.ranges.partition_point(|(lo, _)| *lo <= c);
+        i > 0 && c <= self.ranges[i - 1].1
+    }
+
+    fn as_char(&self) -> Option<char> {
+        match self.ranges[..] {
+            [(lo, hi)] if lo == hi => char::from_u32(lo),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_pattern(&self) -> String {
+        let mut pattern = String::from("[");
+        for &(lo, hi) in &self.ranges {
+            push_class_char(&mut pattern, lo);
+            if hi > lo {
+                if hi > lo + 1 {
+                    pattern.push('-');
+                }
+                push_class_char(&mut pattern, hi);
+            }
+        }
+        pattern.push(']');
+        pattern
+    }
+}
+
+fn push_class_char(pattern: &mut String, code: u32) {
+    match char::from_u32(code) {
+        Some(c) if c.is_ascii_alphanumeric() || (c.is_ascii_punctuation() && !"\\[]^-{}".contains(c)) || c.is_xid_start() => pattern.push(c),
+        _ => pattern.push_str(&format!("\\u{{{:X}}}", code)),
+    }
+}
+
+pub(crate) fn escape_literal(literal: &str) -> String {
+    let mut escaped = String::new();
+    for c in literal.chars() {
+        if r"\^$.*+?()[]{}|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Set(CharSet),
+    Concat(Vec<Pattern>),
+    Alternate(Vec<Pattern>),
+    Star(Box<Pattern>),
+    Plus(Box<Pattern>),
+    Optional(Box<Pattern>),
+}
+
+struct PatternParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    definitions: &'a BTreeMap<String, String>,
+    depth: usize,
+}
+
+impl<'a> PatternParser<'a> {
+    fn parse(pattern: &str, definitions: &'a BTreeMap<String, String>, depth: usize) -> Result<Pattern, String> {
+        if depth > MAX_DEFINITION_DEPTH {
+            return Err("definitions are recursive or nested too deeply".to_string());
+        }
+        let mut parser = PatternParser { chars: pattern.chars().collect(), pos: 0, definitions, depth };
+        let result = parser.alternation()?;
+        match parser.peek() {
+            Some(c) => Err(format!("unexpected `{}` at position {}", c, parser.pos)),
+            None => Ok(result),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn alternation(&mut self) -> Result<Pattern, String> {
+        let mut branches = vec![self.concatenation()?];
+        while self.peek() == Some('|') {
+            self.bump();
+            branches.push(self.concatenation()?);
+        }
+        Ok(if branches.len() == 1 { branches.pop().unwrap() } else { Pattern::Alternate(branches) })
+    }
+
+    fn concatenation(&mut self) -> Result<Pattern, String> {
+        let mut items = Vec::new();
+        while self.peek().is_some_and(|c| c != '|' && c != ')') {
+            items.push(self.repetition()?);
+        }
+        Ok(if items.len() == 1 { items.pop().unwrap() } else { Pattern::Concat(items) })
+    }
+
+    fn repetition(&mut self) -> Result<Pattern, String> {
+        let mut atom = self.atom()?;
+        loop {
+            atom = match self.peek() {
+                Some('*') => Pattern::Star(Box::new(atom)),
+                Some('+') => Pattern::Plus(Box::new(atom)),
+                Some('?') => Pattern::Optional(Box::new(atom)),
+                _ => return Ok(atom),
+            };
+            self.bump();
+        }
+    }
+
+    fn atom(&mut self) -> Result<Pattern, String> {
+        match self.bump() {
+            None => Err("unexpected end of pattern".to_string()),
+            Some('(') => {
+                let inner = self.alternation()?;
+                match self.bump() {
+                    Some(')') => Ok(inner),
+                    _ => Err("unclosed group".to_string()),
+                }
+            }
+            Some('[') => Ok(Pattern::Set(self.class()?)),
+            Some('.') => Ok(Pattern::Set(CharSet::single('\n').negate())),
+            Some('{') => {
+                let mut name = String::new();
+                loop {
+                    match self.bump() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("unclosed definition reference".to_string()),
+                    }
+                }
+                let definition = self.definitions.get(&name).ok_or_else(|| format!("unknown definition `{}`", name))?;
+                PatternParser::parse(definition, self.definitions, self.depth + 1).map_err(|e| format!("in definition `{}`: {}", name, e))
+            }
+            Some(c @ ('*' | '+' | '?')) => Err(format!("nothing to repeat before `{}`", c)),
+            Some('\\') => Ok(Pattern::Set(self.escape()?)),
+            Some(c) => Ok(Pattern::Set(CharSet::single(c))),
+        }
+    }
+
+    fn escape(&mut self) -> Result<CharSet, String> {
+        let c = match self.bump() {
+            None => return Err("trailing backslash".to_string()),
+            Some('s') => return Ok(WHITESPACE.clone()),
+            Some('d') => return Ok(CharSet::new(vec![('0' as u32, '9' as u32)])),
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('f') => '\x0C',
+            Some('v') => '\x0B',
+            Some('0') => '\0',
+            Some('x') => {
+                let digits: String = (0..2).filter_map(|_| self.bump()).collect();
+                let code = u32::from_str_radix(&digits, 16).map_err(|_| format!("invalid escape `\\x{}`", digits))?;
+                char::from_u32(code).unwrap()
+            }
+            Some('u') => {
+                if self.bump() != Some('{') {
+                    return Err("expected `{` after `\\u`".to_string());
+                }
+                let mut digits = String::new();
+                loop {
+                    match self.bump() {
+                        Some('}') => break,
+                        Some(c) => digits.push(c),
+                        None => return Err("unclosed `\\u{` escape".to_string()),
+                    }
+                }
+                u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32).ok_or_else(|| format!("invalid escape `\\u{{{}}}`", digits))?
+            }
+            Some(c) => c,
+        };
+        Ok(CharSet::single(c))
+    }
+
+    fn class_item(&mut self) -> Result<CharSet, String> {
+        match self.bump() {
+            Some('\\') => self.escape(),
+            Some(c) => Ok(CharSet::single(c)),
+            None => Err("unclosed character class".to_string()),
+        }
+    }
+
+    fn class(&mut self) -> Result<CharSet, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.bump();
+        }
+        let mut set = CharSet::default();
+        while self.peek() != Some(']') {
+            let item = self.class_item()?;
+            let is_range = self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']');
+            let item = match item.as_char() {
+                Some(lo) if is_range => {
+                    self.bump();
+                    let hi = self.class_item()?.as_char().ok_or("class range must end with a single character")?;
+                    if hi < lo {
+                        return Err(format!("class range `{}-{}` is out of order", lo, hi));
+                    }
+                    CharSet::new(vec![(lo as u32, hi as u32)])
+                }
+                _ => item,
+            };
+            set = set.union(&item);
+        }
+        self.bump();
+        if set.ranges.is_empty() {
+            return Err("empty character class".to_string());
+        }
+        Ok(if negated { set.negate() } else { set })
+    }
+}
+
+pub(crate) fn parse_class(pattern: &str, definitions: &BTreeMap<String, String>) -> Result<CharSet, String> {
+    match PatternParser::parse(pattern, definitions, 0)? {
+        Pattern::Set(set) => Ok(set),
+        _ => Err(format!("`{}` is not a single character class", pattern)),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct NfaState {
+    epsilon: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+    accept: Option<usize>,
+    reject: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Nfa {
+    states: Vec<NfaState>,
+    sets: Vec<CharSet>,
+    set_ids: HashMap<CharSet, usize>,
+}
+
+impl Nfa {
+    fn add(&mut self) -> usize {
+        self.states.push(NfaState::default());
+        self.states.len() - 1
+    }
+
+    fn epsilon(&mut self, from: usize, to: usize) {
+        self.states[from].epsilon.push(to);
+    }
+
+    fn build(&mut self, pattern: &Pattern) -> (usize, usize) {
+        let (start, end) = (self.add(), self.add());
+        match pattern {
+            Pattern::Set(set) => {
+                let next_id = self.sets.len();
+                let id = *self.set_ids.entry(set.clone()).or_insert(next_id);
+                if id == next_id {
+                    self.sets.push(set.clone());
+                }
+                self.states[start].edges.push((id, end));
+            }
+            Pattern::Concat(items) => {
+                let mut last = start;
+                for item in items {
+                    let (s, e) = self.build(item);
+                    self.epsilon(last, s);
+                    last = e;
+                }
+                self.epsilon(last, end);
+            }
+            Pattern::Alternate(branches) => {
+                for branch in branches {
+                    let (s, e) = self.build(branch);
+                    self.epsilon(start, s);
+                    self.epsilon(e, end);
+                }
+            }
+            Pattern::Star(inner) | Pattern::Plus(inner) | Pattern::Optional(inner) => {
+                let (s, e) = self.build(inner);
+                self.epsilon(start, s);
+                self.epsilon(e, end);
+                if !matches!(pattern, Pattern::Optional(_)) {
+                    self.epsilon(e, s);
+                }
+                if !matches!(pattern, Pattern::Plus(_)) {
+                    self.epsilon(start, end);
+                }
+            }
+        }
+        (start, end)
+    }
+}
+
+struct Closure {
+    marks: Vec<usize>,
+    stamp: usize,
+}
+
+impl Closure {
+    fn of(&mut self, nfa: &Nfa, seeds: &[usize]) -> Vec<usize> {
+        self.stamp += 1;
+        let mut stack = seeds.to_vec();
+        let mut result = Vec::new();
+        while let Some(state) = stack.pop() {
+            if self.marks[state] == self.stamp {
+                continue;
+            }
+            self.marks[state] = self.stamp;
+            result.push(state);
+            stack.extend(&nfa.states[state].epsilon);
+        }
+        result.sort_unstable();
+        result
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    kind: TokenType,
+    skip: bool,
+    not_before: Option<CharSet>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DfaLexer {
+    rules: Vec<CompiledRule>,
+    contexts: Vec<CharSet>,
+    ascii: [u16; 128],
+    intervals: Vec<(u32, u16)>,
+    classes: usize,
+    transitions: Vec<u32>,
+    accept: Vec<Vec<usize>>,
+    starts: Vec<u32>,
+}
+
+impl DfaLexer {
+    pub fn new() -> Result<DfaLexer, SpecError> {
+        Self::with_lexicon(&DEFAULT_LEXICON, LexerOptions::default())
+    }
+
+    pub fn with_lexicon(lexicon: &Lexicon, options: LexerOptions) -> Result<DfaLexer, SpecError> {
+        Self::compile(&TokenSpec::from_lexicon(lexicon, options)?)
+    }
+
+    pub fn compile(spec: &TokenSpec) -> Result<DfaLexer, SpecError> {
+        let mut nfa = Nfa::default();
+        let mut rules = Vec::new();
+        let mut contexts: Vec<CharSet> = Vec::new();
+        let mut rule_starts = Vec::new();
+
+        for (index, rule) in spec.rules.iter().enumerate() {
+            let invalid = |message: String| SpecError::InvalidPattern { rule: format!("rule {} ({})", index, rule.kind), message };
+            let pattern = PatternParser::parse(&rule.pattern, &spec.definitions, 0).map_err(invalid)?;
+            let not_before = rule.not_before.as_deref().map(|p| parse_class(p, &spec.definitions)).transpose().map_err(invalid)?;
+            let not_after = rule.not_after.as_deref().map(|p| parse_class(p, &spec.definitions)).transpose().map_err(invalid)?;
+            let context = not_after.map(|set| match contexts.iter().position(|c| *c == set) {
+                Some(i) => i,
+                None => {
+                    contexts.push(set);
+                    contexts.len() - 1
+                }
+            });
+            if contexts.len() > MAX_CONTEXTS {
+                return Err(SpecError::TooManyContexts(MAX_CONTEXTS));
+            }
+
+            let (start, end) = nfa.build(&pattern);
+            nfa.states[end].accept = Some(index);
+            if let Some(except) = &rule.except {
+                let except = PatternParser::parse(except, &spec.definitions, 0).map_err(invalid)?;
+                let (except_start, except_end) = nfa.build(&except);
+                nfa.states[except_end].reject = Some(index);
+                nfa.epsilon(start, except_start);
+            }
+            rules.push(CompiledRule { kind: rule.kind, skip: rule.skip, not_before });
+            rule_starts.push((start, end, context));
+        }
+
+        let mut closure = Closure { marks: vec![0; nfa.states.len()], stamp: 0 };
+        for (index, &(start, end, _)) in rule_starts.iter().enumerate() {
+            if closure.of(&nfa, &[start]).contains(&end) {
+                let rule = format!("rule {} ({})", index, spec.rules[index].kind);
+                return Err(SpecError::InvalidPattern { rule, message: "pattern matches the empty string".to_string() });
+            }
+        }
+
+        let Alphabet { ascii, intervals, set_classes, classes } = Alphabet::partition(&nfa.sets);
+
+        let mut ids: HashMap<Vec<usize>, usize> = HashMap::new();
+        let mut sets: Vec<Vec<usize>> = vec![Vec::new()];
+        ids.insert(Vec::new(), 0);
+        let mut intern = |set: Vec<usize>, sets: &mut Vec<Vec<usize>>| {
+            let next = sets.len();
+            *ids.entry(set).or_insert_with_key(|set| {
+                sets.push(set.clone());
+                next
+            })
+        };
+
+        let mut starts = Vec::new();
+        for mask in 0..1usize << contexts.len() {
+            let seeds: Vec<usize> = rule_starts
+                .iter()
+                .filter(|(_, _, context)| context.is_none_or(|c| mask & (1 << c) == 0))
+                .map(|(start, _, _)| *start)
+                .collect();
+            let set = closure.of(&nfa, &seeds);
+            starts.push(intern(set, &mut sets));
+        }
+
+        let mut transitions: Vec<usize> = Vec::new();
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); classes];
+        let mut current = 0;
+        while current < sets.len() {
+            for bucket in buckets.iter_mut() {
+                bucket.clear();
+            }
+            for &state in &sets[current] {
+                for &(set, target) in &nfa.states[state].edges {
+                    for &class in &set_classes[set] {
+                        buckets[class].push(target);
+                    }
+                }
+            }
+            for bucket in &buckets {
+                let next = if bucket.is_empty() { 0 } else { intern(closure.of(&nfa, bucket), &mut sets) };
+                transitions.push(next);
+            }
+            current += 1;
+        }
+
+        let accept: Vec<Vec<usize>> = sets
+            .iter()
+            .map(|set| {
+                let rejected: Vec<usize> = set.iter().filter_map(|s| nfa.states[*s].reject).collect();
+                let mut candidates: Vec<usize> = set.iter().filter_map(|s| nfa.states[*s].accept).filter(|r| !rejected.contains(r)).collect();
+                candidates.sort_unstable();
+                if let Some(last) = candidates.iter().position(|r| rules[*r].not_before.is_none()) {
+                    candidates.truncate(last + 1);
+                }
+                candidates
+            })
+            .collect();
+
+        let (blocks, count) = minimise(&transitions, &accept, classes);
+        let mut representatives = vec![usize::MAX; count];
+        for (state, block) in blocks.iter().enumerate() {
+            if representatives[*block] == usize::MAX {
+                representatives[*block] = state;
+            }
+        }
+        let mut minimal = Vec::with_capacity(count * classes);
+        for &state in &representatives {
+            minimal.extend(transitions[state * classes..(state + 1) * classes].iter().map(|t| blocks[*t] as u32));
+        }
+
+        Ok(DfaLexer {
+            rules,
+            contexts,
+            ascii,
+            intervals,
+            classes,
+            transitions: minimal,
+            accept: representatives.iter().map(|s| accept[*s].clone()).collect(),
+            starts: starts.iter().map(|s| blocks[*s] as u32).collect(),
+        })
+    }
+
+    pub fn states(&self) -> usize {
+        self.accept.len()
+    }
+
+    pub fn classes(&self) -> usize {
+        self.classes
+    }
+
+    fn class_of(&self, c: char) -> usize {
+        let code = c as u32;
+        if code < 128 {
+            return self.ascii[code as usize] as usize;
+        }
+        let i = self.intervals.partition_point(|(start, _)| *start <= code);
+        self.intervals[i - 1].1 as usize
+    }
+
+    fn longest_match(&self, input: &str, start: usize) -> Option<(usize, usize)> {
+        let previous = input[..start].chars().next_back();
+        let mask = self.contexts.iter().enumerate().fold(0, |mask, (i, context)| {
+            if previous.is_some_and(|c| context.contains(c)) { mask | 1 << i } else { mask }
+        });
+
+        let mut state = self.starts[mask] as usize;
+        let mut longest = None;
+        let mut chars = input[start..].char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            state = self.transitions[state * self.classes + self.class_of(c)] as usize;
+            if state == 0 {
+                break;
+            }
+            let next = chars.peek().map(|(_, c)| *c);
+            let accepted = self.accept[state]
+                .iter()
+                .find(|r| !self.rules[**r].not_before.as_ref().is_some_and(|set| next.is_some_and(|n| set.contains(n))));
+            if let Some(rule) = accepted {
+                longest = Some((start + offset + c.len_utf8(), *rule));
+            }
+        }
+        longest
+    }
+
+    pub fn tokenize(&self, input: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut position = Position { offset: 0, line: 1, column: 1 };
+        while position.offset < input.len() {
+            let start = position;
+            let (end, kind, skip) = match self.longest_match(input, start.offset) {
+                Some((end, rule)) => (end, self.rules[rule].kind, self.rules[rule].skip),
+                None => (start.offset + input[start.offset..].chars().next().unwrap().len_utf8(), TokenType::Invalid, false),
+            };
+            let content = &input[start.offset..end];
+            for c in content.chars() {
+                if c == '\n' {
+                    position.line += 1;
+                    position.column = 1;
+                } else {
+                    position.column += 1;
+                }
+            }
+            position.offset = end;
+            if !skip {
+                let mut token = Token::new(kind, content.to_string());
+                token.span = Span::new(start, position);
+                tokens.push(token);
+            }
+        }
+        tokens
+    }
+}
+
+struct Alphabet {
+    ascii: [u16; 128],
+    intervals: Vec<(u32, u16)>,
+    set_classes: Vec<Vec<usize>>,
+    classes: usize,
+}
+
+impl Alphabet {
+    fn partition(sets: &[CharSet]) -> Alphabet {
+        let mut boundaries: Vec<u32> = vec![0, LAST_CHAR + 1];
+        for set in sets {
+            for &(lo, hi) in &set.ranges {
+                boundaries.push(lo);
+                boundaries.push(hi + 1);
+            }
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut signatures: Vec<Vec<usize>> = vec![Vec::new(); boundaries.len() - 1];
+        for (id, set) in sets.iter().enumerate() {
+            for &(lo, hi) in &set.ranges {
+                let mut i = boundaries.binary_search(&lo).unwrap();
+                while boundaries[i] <= hi {
+                    signatures[i].push(id);
+                    i += 1;
+                }
+            }
+        }
+
+        let mut class_ids: HashMap<&[usize], usize> = HashMap::new();
+        class_ids.insert(&[], 0);
+        let mut set_classes = vec![Vec::new(); sets.len()];
+        let mut intervals: Vec<(u32, u16)> = Vec::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            let next = class_ids.len();
+            let class = *class_ids.entry(signature).or_insert(next);
+            if class == next {
+                for &id in signature {
+                    set_classes[id].push(class);
+                }
+            }
+            if intervals.last().is_none_or(|(_, last)| *last as usize != class) {
+                intervals.push((boundaries[i], class as u16));
+            }
+        }
+
+        let mut ascii = [0; 128];
+        for (code, class) in ascii.iter_mut().enumerate() {
+            let i = intervals.partition_point(|(start, _)| *start <= code as u32);
+            *class = intervals[i - 1].1;
+        }
+        Alphabet { ascii, intervals, set_classes, classes: class_ids.len() }
+    }
+}
+
+fn minimise(transitions: &[usize], accept: &[Vec<usize>], classes: usize) -> (Vec<usize>, usize) {
+    let states = accept.len();
+    let mut ids: HashMap<&[usize], usize> = HashMap::new();
+    let mut blocks: Vec<usize> = accept
+        .iter()
+        .map(|a| {
+            let next = ids.len();
+            *ids.entry(a).or_insert(next)
+        })
+        .collect();
+    let mut count = ids.len();
+
+    loop {
+        let mut ids: HashMap<Vec<usize>, usize> = HashMap::new();
+        let refined: Vec<usize> = (0..states)
+            .map(|state| {
+                let mut signature = Vec::with_capacity(classes + 1);
+                signature.push(blocks[state]);
+                signature.extend(transitions[state * classes..(state + 1) * classes].iter().map(|t| blocks[*t]));
+                let next = ids.len();
+                *ids.entry(signature).or_insert(next)
+            })
+            .collect();
+        let refined_count = ids.len();
+        blocks = refined;
+        if refined_count == count {
+            return (blocks, count);
+        }
+        count = refined_count;
+    }
+}
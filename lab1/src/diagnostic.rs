@@ -0,0 +1,72 @@
+use std::fmt;
+use crate::error::{LexError, ParseError, RuntimeError, SymbolError};
+use crate::token::Span;
+
+pub trait Diagnostic: fmt::Display {
+    fn span(&self) -> Span;
+
+    fn level(&self) -> &'static str {
+        "error"
+    }
+}
+
+impl Diagnostic for LexError {
+    fn span(&self) -> Span {
+        LexError::span(self)
+    }
+
+    fn level(&self) -> &'static str {
+        if self.is_warning() { "warning" } else { "error" }
+    }
+}
+
+impl Diagnostic for ParseError {
+    fn span(&self) -> Span {
+        ParseError::span(self)
+    }
+}
+
+impl Diagnostic for SymbolError {
+    fn span(&self) -> Span {
+        SymbolError::span(self)
+    }
+
+    fn level(&self) -> &'static str {
+        if self.is_warning() { "warning" } else { "error" }
+    }
+}
+
+impl Diagnostic for RuntimeError {
+    fn span(&self) -> Span {
+        RuntimeError::span(self)
+    }
+}
+
+pub fn render_diagnostic(error: &dyn Diagnostic, source: &str, path: &str) -> String {
+    let span = error.span();
+    let line = source.lines().nth(span.line - 1).unwrap_or("");
+    let line_number = span.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    let padding: String = line
+        .chars()
+        .take(span.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let line_end = source[span.start..].find('\n').map_or(source.len(), |i| span.start + i);
+    let width = source[span.start..span.end.min(line_end)].chars().count().max(1);
+
+    format!(
+        "{}: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+        error.level(), error,
+        gutter, path, span.line, span.column,
+        gutter,
+        line_number, line,
+        gutter, padding, "^".repeat(width),
+    )
+}
+
+pub fn render_diagnostic_location(error: &dyn Diagnostic, path: &str) -> String {
+    let span = error.span();
+    format!("{}: {}\n --> {}:{}:{}\n", error.level(), error, path, span.line, span.column)
+}
@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use crate::token::Span;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LexError {
+    UnterminatedString { span: Span },
+    UnterminatedComment { span: Span },
+    BadStringLiteral { span: Span, reason: String },
+    BadCharLiteral { span: Span, reason: String },
+    IllegalIdentifierChar { span: Span, character: char },
+    BadNumber { span: Span, reason: String },
+    ConfusableCharacter { span: Span, character: char, lookalike: char },
+    ConfusableIdentifier { span: Span, name: String, other: String },
+    TokenTooLong { span: Span, limit: usize },
+}
+
+impl LexError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnterminatedString { span }
+            | LexError::UnterminatedComment { span }
+            | LexError::BadStringLiteral { span, .. }
+            | LexError::BadCharLiteral { span, .. }
+            | LexError::IllegalIdentifierChar { span, .. }
+            | LexError::BadNumber { span, .. }
+            | LexError::ConfusableCharacter { span, .. }
+            | LexError::ConfusableIdentifier { span, .. }
+            | LexError::TokenTooLong { span, .. } => *span,
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        matches!(self, LexError::ConfusableCharacter { .. } | LexError::ConfusableIdentifier { .. })
+    }
+
+    pub fn with_span(mut self, new_span: Span) -> LexError {
+        match &mut self {
+            LexError::UnterminatedString { span }
+            | LexError::UnterminatedComment { span }
+            | LexError::BadStringLiteral { span, .. }
+            | LexError::BadCharLiteral { span, .. }
+            | LexError::IllegalIdentifierChar { span, .. }
+            | LexError::BadNumber { span, .. }
+            | LexError::ConfusableCharacter { span, .. }
+            | LexError::ConfusableIdentifier { span, .. }
+            | LexError::TokenTooLong { span, .. } => *span = new_span,
+        }
+        self
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexError::UnterminatedString { .. } => write!(f, "unterminated string literal"),
+            LexError::UnterminatedComment { .. } => write!(f, "unterminated block comment"),
+            LexError::BadStringLiteral { reason, .. } => write!(f, "invalid string literal: {}", reason),
+            LexError::BadCharLiteral { reason, .. } => write!(f, "invalid character literal: {}", reason),
+            LexError::IllegalIdentifierChar { character, .. } => write!(f, "illegal character `{}` in identifier", character),
+            LexError::BadNumber { reason, .. } => write!(f, "malformed number: {}", reason),
+            LexError::ConfusableCharacter { character, lookalike, .. } => write!(
+                f, "identifier contains `{}` (U+{:04X}), which looks like `{}` (U+{:04X})",
+                character, *character as u32, lookalike, *lookalike as u32
+            ),
+            LexError::ConfusableIdentifier { name, other, .. } => write!(f, "identifier `{}` is confusable with `{}`", name, other),
+            LexError::TokenTooLong { limit, .. } => write!(f, "token exceeds the maximum length of {} bytes", limit),
+        }
+    }
+}
+
+impl Error for LexError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedToken { span: Span, expected: String, found: String },
+    UnexpectedEof { span: Span, expected: String },
+    ChainedComparison { span: Span },
+    InvalidAssignmentTarget { span: Span },
+    NestingTooDeep { span: Span },
+}
+
+impl ParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken { span, .. }
+            | ParseError::UnexpectedEof { span, .. }
+            | ParseError::ChainedComparison { span }
+            | ParseError::InvalidAssignmentTarget { span }
+            | ParseError::NestingTooDeep { span } => *span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { expected, found, .. } => write!(f, "expected {}, found `{}`", expected, found),
+            ParseError::UnexpectedEof { expected, .. } => write!(f, "expected {}, found end of input", expected),
+            ParseError::ChainedComparison { .. } => write!(f, "comparison operators cannot be chained"),
+            ParseError::InvalidAssignmentTarget { .. } => write!(f, "invalid left-hand side of assignment"),
+            ParseError::NestingTooDeep { .. } => write!(f, "nesting is too deep"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolError {
+    Undeclared { span: Span, name: String },
+    Unused { span: Span, name: String },
+}
+
+impl SymbolError {
+    pub fn span(&self) -> Span {
+        match self {
+            SymbolError::Undeclared { span, .. } | SymbolError::Unused { span, .. } => *span,
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        matches!(self, SymbolError::Unused { .. })
+    }
+}
+
+impl fmt::Display for SymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SymbolError::Undeclared { name, .. } => write!(f, "cannot find `{}` in this scope", name),
+            SymbolError::Unused { name, .. } => write!(f, "unused variable `{}`", name),
+        }
+    }
+}
+
+impl Error for SymbolError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeError {
+    Undefined { span: Span, name: String },
+    Uninitialized { span: Span, name: String },
+    Immutable { span: Span, name: String },
+    TypeMismatch { span: Span, expected: String, found: String },
+    InvalidOperand { span: Span, op: String, ty: String },
+    OutOfRange { span: Span, value: String, ty: String },
+    Overflow { span: Span, op: String, ty: String },
+    DivisionByZero { span: Span },
+    ArgumentCount { span: Span, name: String, expected: usize, found: usize },
+    RecursionLimit { span: Span, limit: usize },
+    OutsideLoop { span: Span, keyword: String },
+    Format { span: Span, reason: String },
+    Unsupported { span: Span, what: String },
+    Output { span: Span, message: String },
+}
+
+impl RuntimeError {
+    pub fn span(&self) -> Span {
+        match self {
+            RuntimeError::Undefined { span, .. }
+            | RuntimeError::Uninitialized { span, .. }
+            | RuntimeError::Immutable { span, .. }
+            | RuntimeError::TypeMismatch { span, .. }
+            | RuntimeError::InvalidOperand { span, .. }
+            | RuntimeError::OutOfRange { span, .. }
+            | RuntimeError::Overflow { span, .. }
+            | RuntimeError::DivisionByZero { span }
+            | RuntimeError::ArgumentCount { span, .. }
+            | RuntimeError::RecursionLimit { span, .. }
+            | RuntimeError::OutsideLoop { span, .. }
+            | RuntimeError::Format { span, .. }
+            | RuntimeError::Unsupported { span, .. }
+            | RuntimeError::Output { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuntimeError::Undefined { name, .. } => write!(f, "cannot find `{}` in this scope", name),
+            RuntimeError::Uninitialized { name, .. } => write!(f, "used binding `{}` before it was initialized", name),
+            RuntimeError::Immutable { name, .. } => write!(f, "cannot assign twice to immutable variable `{}`", name),
+            RuntimeError::TypeMismatch { expected, found, .. } => write!(f, "mismatched types: expected `{}`, found `{}`", expected, found),
+            RuntimeError::InvalidOperand { op, ty, .. } => write!(f, "cannot apply `{}` to a value of type `{}`", op, ty),
+            RuntimeError::OutOfRange { value, ty, .. } => write!(f, "value {} is out of range for `{}`", value, ty),
+            RuntimeError::Overflow { op, ty, .. } => write!(f, "attempt to {} with overflow in `{}`", op, ty),
+            RuntimeError::DivisionByZero { .. } => write!(f, "attempt to divide by zero"),
+            RuntimeError::ArgumentCount { name, expected, found, .. } => {
+                write!(f, "function `{}` takes {} argument(s) but {} were supplied", name, expected, found)
+            }
+            RuntimeError::RecursionLimit { limit, .. } => write!(f, "recursion limit of {} nested calls reached", limit),
+            RuntimeError::OutsideLoop { keyword, .. } => write!(f, "`{}` outside of a loop", keyword),
+            RuntimeError::Format { reason, .. } => write!(f, "invalid format string: {}", reason),
+            RuntimeError::Unsupported { what, .. } => write!(f, "{} is not supported by the interpreter", what),
+            RuntimeError::Output { message, .. } => write!(f, "cannot write program output: {}", message),
+        }
+    }
+}
+
+impl Error for RuntimeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    InvalidRange { range: Range<usize>, len: usize },
+    NotCharBoundary { offset: usize },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EditError::InvalidRange { range, len } => {
+                write!(f, "cannot apply edit: range {}..{} is invalid for a source of {} bytes", range.start, range.end, len)
+            }
+            EditError::NotCharBoundary { offset } => write!(f, "cannot apply edit: byte offset {} is not on a character boundary", offset),
+        }
+    }
+}
+
+impl Error for EditError {}
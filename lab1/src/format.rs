@@ -0,0 +1,177 @@
+use crate::config::{Lexicon, DEFAULT_LEXICON};
+use crate::lexer::{lex_with_lexicon, LexerOptions};
+use crate::token::{Token, TokenType};
+
+const INDENT: &str = "    ";
+const MAX_NEWLINES: usize = 2;
+
+pub fn format_source(source: &str) -> String {
+    format_source_with_lexicon(source, &DEFAULT_LEXICON, LexerOptions::default())
+}
+
+pub fn format_source_with_lexicon(source: &str, lexicon: &Lexicon, options: LexerOptions) -> String {
+    let options = LexerOptions { trivia: true, ..options };
+    let output = lex_with_lexicon(source, lexicon, options);
+    Formatter::new(lexicon, options).format(&output.tokens)
+}
+
+struct Formatter<'a> {
+    lexicon: &'a Lexicon,
+    options: LexerOptions,
+    output: String,
+    depth: usize,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(lexicon: &'a Lexicon, options: LexerOptions) -> Self {
+        Formatter { lexicon, options, output: String::new(), depth: 0 }
+    }
+
+    fn format(mut self, tokens: &[Token]) -> String {
+        let mut previous: Option<&Token> = None;
+        let mut previous_unary = false;
+        let (mut newlines, mut spaced) = (0, false);
+
+        for token in tokens {
+            match token.type_name {
+                TokenType::Newline => {
+                    newlines += 1;
+                    continue;
+                }
+                TokenType::Whitespace => {
+                    spaced = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let closes = matches!(symbol(token), Some("}" | ")" | "]"));
+            match previous {
+                Some(_) if newlines > 0 => {
+                    self.output.push_str(&"\n".repeat(newlines.min(MAX_NEWLINES)));
+                    self.indent(closes);
+                }
+                Some(prev) if is_signed_literal(token) && ends_operand(prev) => {
+                    self.output.push(' ');
+                    self.output.push_str(&token.content[..1]);
+                    self.output.push(' ');
+                    self.output.push_str(&token.content[1..]);
+                    self.track_depth(token);
+                    previous = Some(token);
+                    previous_unary = false;
+                    (newlines, spaced) = (0, false);
+                    continue;
+                }
+                Some(prev) => {
+                    if self.wants_space(prev, previous_unary, token, spaced) || self.would_merge(prev, token) {
+                        self.output.push(' ');
+                    }
+                }
+                None => self.indent(closes),
+            }
+
+            self.output.push_str(&token.content);
+            self.track_depth(token);
+            previous_unary = is_prefix_operator(token) && !previous.is_some_and(ends_operand);
+            previous = Some(token);
+            (newlines, spaced) = (0, false);
+        }
+
+        if previous.is_some_and(|last| !self.would_merge_newline(last)) {
+            self.output.push('\n');
+        }
+        self.output
+    }
+
+    fn indent(&mut self, closes: bool) {
+        let depth = if closes { self.depth.saturating_sub(1) } else { self.depth };
+        self.output.push_str(&INDENT.repeat(depth));
+    }
+
+    fn track_depth(&mut self, token: &Token) {
+        match symbol(token) {
+            Some("{" | "(" | "[") => self.depth += 1,
+            Some("}" | ")" | "]") => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn wants_space(&self, prev: &Token, prev_unary: bool, next: &Token, spaced: bool) -> bool {
+        if prev.type_name == TokenType::Comment || next.type_name == TokenType::Comment {
+            return true;
+        }
+        if is_angle(prev) && symbol(next) == Some("{") {
+            return true;
+        }
+        if is_angle(prev) || is_angle(next) {
+            return spaced;
+        }
+        if prev_unary {
+            return false;
+        }
+        match (symbol(prev), symbol(next)) {
+            (Some("#" | "#!" | "::" | "."), _) | (_, Some("::" | ".")) => false,
+            (Some(".." | "..=" | "..."), next) => next == Some("{"),
+            (_, Some(".." | "..=" | "...")) => !ends_operand(prev),
+            (_, Some("," | ";" | ":" | "?")) => false,
+            (Some("(" | "["), _) | (_, Some(")" | "]")) => false,
+            (Some("{"), Some("}")) => false,
+            (_, Some("(" | "[")) => !ends_operand(prev),
+            _ => true,
+        }
+    }
+
+    fn would_merge(&self, prev: &Token, next: &Token) -> bool {
+        let joined = format!("{}{}", prev.content, next.content);
+        let options = LexerOptions { keep_comments: true, trivia: false, ..self.options };
+        let tokens = lex_with_lexicon(&joined, self.lexicon, options).tokens;
+        tokens.len() != 2 || tokens[0].content != prev.content || tokens[1].content != next.content
+    }
+
+    fn would_merge_newline(&self, last: &Token) -> bool {
+        let joined = format!("{}\n", last.content);
+        let options = LexerOptions { keep_comments: true, trivia: false, ..self.options };
+        let tokens = lex_with_lexicon(&joined, self.lexicon, options).tokens;
+        tokens.len() != 1 || tokens[0].content != last.content
+    }
+}
+
+fn symbol(token: &Token) -> Option<&str> {
+    match token.type_name {
+        TokenType::Arithmetic
+        | TokenType::Comparison
+        | TokenType::Logical
+        | TokenType::Assignment
+        | TokenType::Delimiter
+        | TokenType::Attribute => Some(&token.content),
+        _ => None,
+    }
+}
+
+fn is_angle(token: &Token) -> bool {
+    matches!(symbol(token), Some("<" | ">" | ">>"))
+}
+
+fn is_prefix_operator(token: &Token) -> bool {
+    matches!(symbol(token), Some("-" | "!" | "*" | "&" | "&&"))
+}
+
+fn is_signed_literal(token: &Token) -> bool {
+    matches!(token.type_name, TokenType::Integer | TokenType::Double) && token.content.starts_with(['-', '+'])
+}
+
+fn ends_operand(token: &Token) -> bool {
+    match token.type_name {
+        TokenType::Identifier
+        | TokenType::Function
+        | TokenType::DataType
+        | TokenType::Macro
+        | TokenType::Integer
+        | TokenType::Double
+        | TokenType::Boolean
+        | TokenType::StringLiteral
+        | TokenType::CharLiteral => true,
+        TokenType::Invalid => token.content.chars().next().is_some_and(char::is_alphanumeric),
+        _ => matches!(symbol(token), Some(")" | "]" | "?")),
+    }
+}
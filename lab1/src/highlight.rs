@@ -0,0 +1,132 @@
+use crate::token::{Token, TokenType};
+
+const STYLESHEET: &str = "\
+body { background: #fafafa; }
+pre.lab1 { font-family: monospace; font-size: 14px; line-height: 1.4; padding: 12px; background: #fff; border: 1px solid #ddd; }
+.tok-data-type { color: #00838f; }
+.tok-integer, .tok-double, .tok-boolean { color: #e65100; }
+.tok-identifier { color: #212121; }
+.tok-arithmetic, .tok-comparison, .tok-logical, .tok-assignment { color: #616161; }
+.tok-function { color: #1565c0; }
+.tok-keyword { color: #6a1b9a; font-weight: bold; }
+.tok-delimiter { color: #757575; }
+.tok-string, .tok-char { color: #2e7d32; }
+.tok-comment { color: #9e9e9e; font-style: italic; }
+.tok-lifetime { color: #00838f; font-style: italic; }
+.tok-attribute { color: #f9a825; }
+.tok-macro { color: #1565c0; font-weight: bold; }
+.tok-invalid { color: #fff; background: #d32f2f; }
+";
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+pub fn css_class(token_type: TokenType) -> &'static str {
+    match token_type {
+        TokenType::DataType => "tok-data-type",
+        TokenType::Integer => "tok-integer",
+        TokenType::Double => "tok-double",
+        TokenType::Identifier => "tok-identifier",
+        TokenType::Arithmetic => "tok-arithmetic",
+        TokenType::Function => "tok-function",
+        TokenType::KeyWord => "tok-keyword",
+        TokenType::Delimiter => "tok-delimiter",
+        TokenType::Comparison => "tok-comparison",
+        TokenType::Logical => "tok-logical",
+        TokenType::Assignment => "tok-assignment",
+        TokenType::Boolean => "tok-boolean",
+        TokenType::StringLiteral => "tok-string",
+        TokenType::CharLiteral => "tok-char",
+        TokenType::Comment => "tok-comment",
+        TokenType::Lifetime => "tok-lifetime",
+        TokenType::Attribute => "tok-attribute",
+        TokenType::Macro => "tok-macro",
+        TokenType::Whitespace => "tok-whitespace",
+        TokenType::Newline => "tok-newline",
+        TokenType::Invalid => "tok-invalid",
+    }
+}
+
+pub fn ansi_color(token_type: TokenType) -> &'static str {
+    match token_type {
+        TokenType::DataType => "\x1b[36m",
+        TokenType::Integer | TokenType::Double | TokenType::Boolean => "\x1b[33m",
+        TokenType::Identifier => "",
+        TokenType::Arithmetic | TokenType::Comparison | TokenType::Logical | TokenType::Assignment => "\x1b[37m",
+        TokenType::Function => "\x1b[34m",
+        TokenType::KeyWord => "\x1b[1;35m",
+        TokenType::Delimiter => "\x1b[90m",
+        TokenType::StringLiteral | TokenType::CharLiteral => "\x1b[32m",
+        TokenType::Comment => "\x1b[3;90m",
+        TokenType::Lifetime => "\x1b[3;36m",
+        TokenType::Attribute => "\x1b[93m",
+        TokenType::Macro => "\x1b[1;34m",
+        TokenType::Whitespace | TokenType::Newline => "",
+        TokenType::Invalid => "\x1b[1;97;41m",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn segments<'s>(source: &'s str, tokens: &[Token]) -> Vec<(&'s str, Option<TokenType>)> {
+    let mut last = 0;
+    let mut parts = Vec::with_capacity(tokens.len() * 2 + 1);
+    for token in tokens {
+        if token.span.start < last || token.span.end > source.len() {
+            continue;
+        }
+        if token.span.start > last {
+            parts.push((&source[last..token.span.start], None));
+        }
+        parts.push((&source[token.span.start..token.span.end], Some(token.type_name)));
+        last = token.span.end;
+    }
+    if last < source.len() {
+        parts.push((&source[last..], None));
+    }
+    parts
+}
+
+pub fn render_html(source: &str, tokens: &[Token], title: &str) -> String {
+    let mut body = String::new();
+    for (text, token_type) in segments(source, tokens) {
+        match token_type {
+            Some(token_type) => body.push_str(&format!(
+                "<span class=\"{}\" title=\"{}\">{}</span>",
+                css_class(token_type), token_type, escape_html(text)
+            )),
+            None => body.push_str(&escape_html(text)),
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<pre class=\"lab1\">{}</pre>\n</body>\n</html>\n",
+        escape_html(title), STYLESHEET, body
+    )
+}
+
+pub fn render_ansi(source: &str, tokens: &[Token]) -> String {
+    let mut output = String::with_capacity(source.len() * 2);
+    for (text, token_type) in segments(source, tokens) {
+        match token_type.map(ansi_color) {
+            Some(color) if !color.is_empty() => {
+                output.push_str(color);
+                output.push_str(text);
+                output.push_str(ANSI_RESET);
+            }
+            _ => output.push_str(text),
+        }
+    }
+    output
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Range;
+use crate::config::{Lexicon, DEFAULT_LEXICON};
+use crate::confusables::{skeleton, Skeletons};
+use crate::error::{EditError, LexError};
+use crate::lexer::{lex_with_lexicon, LexOutput, Lexer, LexerOptions};
+use crate::token::{Position, Span, TokenType};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: &str) -> Self {
+        TextEdit { range, text: text.to_string() }
+    }
+
+    pub fn apply(&self, source: &str) -> Result<String, EditError> {
+        if self.range.start > self.range.end || self.range.end > source.len() {
+            return Err(EditError::InvalidRange { range: self.range.clone(), len: source.len() });
+        }
+        if let Some(offset) = [self.range.start, self.range.end].into_iter().find(|&offset| !source.is_char_boundary(offset)) {
+            return Err(EditError::NotCharBoundary { offset });
+        }
+        let mut edited = String::with_capacity(source.len() - self.range.len() + self.text.len());
+        edited.push_str(&source[..self.range.start]);
+        edited.push_str(&self.text);
+        edited.push_str(&source[self.range.end..]);
+        Ok(edited)
+    }
+
+    fn new_end(&self) -> usize {
+        self.range.start + self.text.len()
+    }
+}
+
+struct Shift {
+    offset: isize,
+    lines: isize,
+    line: usize,
+    columns: isize,
+}
+
+impl Shift {
+    fn between(old: Span, new: Span) -> Shift {
+        Shift {
+            offset: new.start as isize - old.start as isize,
+            lines: new.line as isize - old.line as isize,
+            line: old.line,
+            columns: new.column as isize - old.column as isize,
+        }
+    }
+
+    fn apply(&self, span: Span) -> Span {
+        let column = if span.line == self.line { span.column as isize + self.columns } else { span.column as isize };
+        Span {
+            start: (span.start as isize + self.offset) as usize,
+            end: (span.end as isize + self.offset) as usize,
+            line: (span.line as isize + self.lines) as usize,
+            column: column as usize,
+        }
+    }
+}
+
+pub fn relex(source: &str, previous: LexOutput, edit: &TextEdit) -> LexOutput {
+    relex_with_options(source, previous, edit, LexerOptions::default())
+}
+
+pub fn relex_with_options(source: &str, previous: LexOutput, edit: &TextEdit, options: LexerOptions) -> LexOutput {
+    relex_with_lexicon(source, previous, edit, &DEFAULT_LEXICON, options)
+}
+
+pub fn relex_with_lexicon(source: &str, previous: LexOutput, edit: &TextEdit, lexicon: &Lexicon, options: LexerOptions) -> LexOutput {
+    if previous.lookahead.len() != previous.tokens.len() {
+        return lex_with_lexicon(source, lexicon, options);
+    }
+    let LexOutput { mut tokens, mut diagnostics, mut lookahead } = previous;
+
+    let dirty = tokens
+        .iter()
+        .zip(&lookahead)
+        .position(|(token, lookahead)| token.span.end + lookahead > edit.range.start)
+        .unwrap_or(tokens.len());
+    let start = match dirty.checked_sub(1) {
+        Some(clean) => end_position(source, tokens[clean].span),
+        None => Position { offset: 0, line: 1, column: 1 },
+    };
+
+    let mut old_tokens = tokens.split_off(dirty);
+    let old_lookahead = lookahead.split_off(dirty);
+    let mut old_diagnostics = diagnostics.split_off(diagnostics.partition_point(|e| e.span().start < start.offset));
+    let mut output = LexOutput { tokens, diagnostics, lookahead };
+
+    let delta = edit.new_end() as isize - edit.range.end as isize;
+    let mut lexer = Lexer::with_lexicon(source, lexicon, options);
+    lexer.restore(start);
+    while let Some(result) = lexer.next() {
+        let span = result.as_ref().map_or_else(|error| error.span(), |token| token.span);
+        if span.start > edit.new_end() {
+            let old_start = (span.start as isize - delta) as usize;
+            if let Ok(index) = old_tokens.binary_search_by_key(&old_start, |token| token.span.start) {
+                let shift = Shift::between(old_tokens[index].span, span);
+                output.tokens.extend(old_tokens.drain(index..).map(|mut token| {
+                    token.span = shift.apply(token.span);
+                    token
+                }));
+                output.lookahead.extend_from_slice(&old_lookahead[index..]);
+                let resumed = old_diagnostics.partition_point(|e| e.span().start < old_start);
+                output.diagnostics.extend(old_diagnostics.drain(resumed..).map(|e| {
+                    let span = shift.apply(e.span());
+                    e.with_span(span)
+                }));
+                break;
+            }
+        }
+        output.push(&mut lexer, result);
+    }
+
+    recheck_confusable_identifiers(&mut output);
+    output
+}
+
+fn end_position(source: &str, span: Span) -> Position {
+    let text = &source[span.start..span.end];
+    match text.rfind('\n') {
+        Some(newline) => Position {
+            offset: span.end,
+            line: span.line + text.matches('\n').count(),
+            column: text[newline + 1..].chars().count() + 1,
+        },
+        None => Position { offset: span.end, line: span.line, column: span.column + text.chars().count() },
+    }
+}
+
+fn recheck_confusable_identifiers(output: &mut LexOutput) {
+    let identifiers = output.tokens.iter().filter(|t| t.type_name == TokenType::Identifier);
+    let confusable: HashSet<String> = identifiers
+        .clone()
+        .filter(|t| !t.content.is_ascii())
+        .map(|t| skeleton(&t.content))
+        .filter(|skeleton| skeleton.is_ascii())
+        .collect();
+
+    let mut skeletons = Skeletons::default();
+    let mut warnings = Vec::new();
+    for token in identifiers.filter(|t| !t.content.is_ascii() || confusable.contains(&t.content)) {
+        if let Some(other) = skeletons.confusable_with(&token.content) {
+            warnings.push(LexError::ConfusableIdentifier { span: token.span, name: token.content.clone(), other });
+        }
+    }
+
+    let diagnostics = mem::take(&mut output.diagnostics);
+    let mut warnings = warnings.into_iter().peekable();
+    for diagnostic in diagnostics.into_iter().filter(|e| !matches!(e, LexError::ConfusableIdentifier { .. })) {
+        while let Some(warning) = warnings.next_if(|w| w.span().start < diagnostic.span().start) {
+            output.diagnostics.push(warning);
+        }
+        output.diagnostics.push(diagnostic);
+    }
+    output.diagnostics.extend(warnings);
+}
@@ -0,0 +1,938 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::iter;
+use std::mem;
+use std::panic;
+use std::thread;
+use crate::ast::*;
+use crate::error::RuntimeError;
+use crate::token::{LiteralValue, Span};
+
+const MAX_CALL_DEPTH: usize = 1000;
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+const MATH_FUNCTIONS: [&str; 5] = ["sin", "cos", "tan", "ctan", "sqrt"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    pub fn from_name(name: &str) -> Option<IntType> {
+        let ty = match name {
+            "i8" => IntType::I8,
+            "i16" => IntType::I16,
+            "i32" => IntType::I32,
+            "i64" => IntType::I64,
+            "i128" => IntType::I128,
+            "isize" => IntType::Isize,
+            "u8" => IntType::U8,
+            "u16" => IntType::U16,
+            "u32" => IntType::U32,
+            "u64" => IntType::U64,
+            "u128" => IntType::U128,
+            "usize" => IntType::Usize,
+            _ => return None,
+        };
+        Some(ty)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+            IntType::Isize | IntType::Usize => usize::BITS,
+            IntType::I128 | IntType::U128 => 128,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize)
+    }
+
+    pub fn min(self) -> i128 {
+        match (self.is_signed(), self.bits()) {
+            (false, _) => 0,
+            (true, 128) => i128::MIN,
+            (true, bits) => -(1i128 << (bits - 1)),
+        }
+    }
+
+    pub fn max(self) -> i128 {
+        match (self.is_signed(), self.bits()) {
+            (_, 128) => i128::MAX,
+            (true, bits) => (1i128 << (bits - 1)) - 1,
+            (false, bits) => (1i128 << bits) - 1,
+        }
+    }
+
+    pub fn contains(self, value: i128) -> bool {
+        (self.min()..=self.max()).contains(&value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatType {
+    F32,
+    F64,
+}
+
+impl FloatType {
+    pub fn from_name(name: &str) -> Option<FloatType> {
+        match name {
+            "f32" => Some(FloatType::F32),
+            "f64" => Some(FloatType::F64),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FloatType::F32 => "f32",
+            FloatType::F64 => "f64",
+        }
+    }
+
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            FloatType::F32 => value as f32 as f64,
+            FloatType::F64 => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Int(IntType),
+    Float(FloatType),
+    Bool,
+    Char,
+    Str,
+    Unit,
+}
+
+impl ValueType {
+    pub fn from_type(ty: &Type) -> Option<ValueType> {
+        match &ty.kind {
+            TypeKind::Path { path, generics } if path.segments.len() == 1 && generics.is_empty() => {
+                let name = path.segments[0].name.as_str();
+                match name {
+                    "bool" => Some(ValueType::Bool),
+                    "char" => Some(ValueType::Char),
+                    "String" | "str" => Some(ValueType::Str),
+                    _ => IntType::from_name(name).map(ValueType::Int).or_else(|| FloatType::from_name(name).map(ValueType::Float)),
+                }
+            }
+            TypeKind::Reference { mutable: false, inner, .. } => ValueType::from_type(inner).filter(|ty| *ty == ValueType::Str),
+            TypeKind::Tuple(elements) if elements.is_empty() => Some(ValueType::Unit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueType::Int(ty) => write!(f, "{}", ty.name()),
+            ValueType::Float(ty) => write!(f, "{}", ty.name()),
+            ValueType::Bool => write!(f, "bool"),
+            ValueType::Char => write!(f, "char"),
+            ValueType::Str => write!(f, "String"),
+            ValueType::Unit => write!(f, "()"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int { value: i128, ty: Option<IntType> },
+    Float { value: f64, ty: Option<FloatType> },
+    Bool(bool),
+    Char(char),
+    Str(String),
+    Unit,
+}
+
+impl Value {
+    pub fn value_type(&self) -> Option<ValueType> {
+        match self {
+            Value::Int { ty, .. } => ty.map(ValueType::Int),
+            Value::Float { ty, .. } => ty.map(ValueType::Float),
+            Value::Bool(_) => Some(ValueType::Bool),
+            Value::Char(_) => Some(ValueType::Char),
+            Value::Str(_) => Some(ValueType::Str),
+            Value::Unit => Some(ValueType::Unit),
+        }
+    }
+
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Int { ty: None, .. } => "{integer}".to_string(),
+            Value::Float { ty: None, .. } => "{float}".to_string(),
+            value => value.value_type().unwrap().to_string(),
+        }
+    }
+
+    pub fn debug(&self) -> String {
+        match self {
+            Value::Float { value, ty: Some(FloatType::F32) } => format!("{:?}", *value as f32),
+            Value::Float { value, .. } => format!("{:?}", value),
+            Value::Char(c) => format!("{:?}", c),
+            Value::Str(s) => format!("{:?}", s),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int { value, ty: Some(IntType::U128) } => write!(f, "{}", *value as u128),
+            Value::Int { value, .. } => write!(f, "{}", value),
+            Value::Float { value, ty: Some(FloatType::F32) } => write!(f, "{}", *value as f32),
+            Value::Float { value, .. } => write!(f, "{}", value),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+pub fn interpret(program: &Program, out: &mut (dyn Write + Send)) -> Result<Value, RuntimeError> {
+    interpret_function(program, "main", out)
+}
+
+pub fn interpret_function(program: &Program, entry: &str, out: &mut (dyn Write + Send)) -> Result<Value, RuntimeError> {
+    thread::scope(|scope| {
+        let worker = thread::Builder::new()
+            .name("lab1-interpreter".to_string())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || run(program, entry, out))
+            .expect("failed to spawn the interpreter thread");
+        worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+fn run(program: &Program, entry: &str, out: &mut dyn Write) -> Result<Value, RuntimeError> {
+    let mut interpreter = Interpreter { functions: HashMap::new(), scopes: Vec::new(), depth: 0, out };
+    interpreter.register(&program.items);
+    let function = interpreter.functions.get(entry).copied().ok_or_else(|| RuntimeError::Undefined {
+        span: Span { start: 0, end: 0, line: 1, column: 1 },
+        name: entry.to_string(),
+    })?;
+    interpreter.call(function, Vec::new(), function.name.span)
+}
+
+enum Flow {
+    Break { label: Option<String>, value: Value, span: Span },
+    Continue { label: Option<String>, span: Span },
+    Return(Value),
+    Error(RuntimeError),
+}
+
+impl From<RuntimeError> for Flow {
+    fn from(error: RuntimeError) -> Flow {
+        Flow::Error(error)
+    }
+}
+
+type Eval = Result<Value, Flow>;
+
+struct Binding {
+    name: String,
+    value: Option<Value>,
+    ty: Option<ValueType>,
+    mutable: bool,
+}
+
+struct Interpreter<'a> {
+    functions: HashMap<String, &'a Function>,
+    scopes: Vec<Vec<Binding>>,
+    depth: usize,
+    out: &'a mut dyn Write,
+}
+
+impl<'a> Interpreter<'a> {
+    fn register(&mut self, items: impl IntoIterator<Item = &'a Item>) {
+        for item in items {
+            if let ItemKind::Function(function) = &item.kind {
+                self.functions.insert(function.name.name.clone(), function);
+            }
+        }
+    }
+
+    fn declare(&mut self, name: &Ident, value: Option<Value>, ty: Option<ValueType>, mutable: bool) {
+        let ty = ty.or_else(|| value.as_ref().and_then(Value::value_type));
+        self.scopes.last_mut().unwrap().push(Binding { name: name.name.clone(), value, ty, mutable });
+    }
+
+    fn lookup(&mut self, name: &Ident) -> Result<&mut Binding, RuntimeError> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.iter_mut().rev())
+            .find(|binding| binding.name == name.name)
+            .ok_or_else(|| RuntimeError::Undefined { span: name.span, name: name.name.clone() })
+    }
+
+    fn call(&mut self, function: &'a Function, args: Vec<Value>, span: Span) -> Result<Value, RuntimeError> {
+        if args.len() != function.params.len() {
+            return Err(RuntimeError::ArgumentCount {
+                span,
+                name: function.name.name.clone(),
+                expected: function.params.len(),
+                found: args.len(),
+            });
+        }
+        if self.depth == MAX_CALL_DEPTH {
+            return Err(RuntimeError::RecursionLimit { span, limit: MAX_CALL_DEPTH });
+        }
+        let return_type = match &function.return_type {
+            Some(ty) => declared_type(ty)?,
+            None => ValueType::Unit,
+        };
+
+        let saved = mem::replace(&mut self.scopes, vec![Vec::new()]);
+        self.depth += 1;
+        let result = self.call_body(function, args);
+        self.depth -= 1;
+        self.scopes = saved;
+
+        let value = match result {
+            Ok(value) | Err(Flow::Return(value)) => value,
+            Err(Flow::Break { span, .. }) => return Err(RuntimeError::OutsideLoop { span, keyword: "break".to_string() }),
+            Err(Flow::Continue { span, .. }) => return Err(RuntimeError::OutsideLoop { span, keyword: "continue".to_string() }),
+            Err(Flow::Error(error)) => return Err(error),
+        };
+        let span = function.body.tail.as_ref().map_or(function.body.span, |tail| tail.span);
+        coerce(value, return_type, span)
+    }
+
+    fn call_body(&mut self, function: &'a Function, args: Vec<Value>) -> Eval {
+        for (param, arg) in function.params.iter().zip(args) {
+            let ty = param.ty.as_ref().map(declared_type).transpose()?;
+            let arg = match ty {
+                Some(ty) => coerce(arg, ty, param.span)?,
+                None => arg,
+            };
+            self.declare(&param.name, Some(arg), ty, param.mutable);
+        }
+        let return_type = function.return_type.as_ref().and_then(ValueType::from_type);
+        self.eval_block_with(&function.body, return_type)
+    }
+
+    fn eval_block(&mut self, block: &'a Block) -> Eval {
+        self.eval_block_with(block, None)
+    }
+
+    fn eval_block_with(&mut self, block: &'a Block, expected: Option<ValueType>) -> Eval {
+        self.scopes.push(Vec::new());
+        let result = self.eval_block_body(block, expected);
+        self.scopes.pop();
+        result
+    }
+
+    fn eval_block_body(&mut self, block: &'a Block, expected: Option<ValueType>) -> Eval {
+        self.register(block.statements.iter().filter_map(|statement| match &statement.kind {
+            StmtKind::Item(item) => Some(item),
+            _ => None,
+        }));
+        for statement in &block.statements {
+            self.eval_statement(statement)?;
+        }
+        match &block.tail {
+            Some(tail) => self.eval_with(tail, expected),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn eval_statement(&mut self, statement: &'a Stmt) -> Result<(), Flow> {
+        match &statement.kind {
+            StmtKind::Let { mutable, name, ty, init } => {
+                let ty = ty.as_ref().map(declared_type).transpose()?;
+                let value = match (init, ty) {
+                    (Some(init), Some(ty)) => Some(coerce(self.eval_with(init, Some(ty))?, ty, init.span)?),
+                    (Some(init), None) => Some(self.eval(init)?),
+                    (None, _) => None,
+                };
+                if name.name != "_" {
+                    self.declare(name, value, ty, *mutable);
+                }
+            }
+            StmtKind::Item(_) => {}
+            StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                self.eval(expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn eval_bool(&mut self, expr: &'a Expr) -> Result<bool, Flow> {
+        match self.eval(expr)? {
+            Value::Bool(b) => Ok(b),
+            value => Err(type_mismatch("bool", &value, expr.span).into()),
+        }
+    }
+
+    fn eval(&mut self, expr: &'a Expr) -> Eval {
+        let span = expr.span;
+        match &expr.kind {
+            ExprKind::Literal(literal) => Ok(literal_value(literal, span)?),
+            ExprKind::Bool(b) => Ok(Value::Bool(*b)),
+            ExprKind::Path(path) if path.segments.len() == 1 => Ok(self.read(&path.segments[0])?),
+            ExprKind::Paren(inner) => self.eval(inner),
+            ExprKind::Tuple(elements) if elements.is_empty() => Ok(Value::Unit),
+            ExprKind::Unary { op, operand } => {
+                let value = self.eval(operand)?;
+                Ok(unary(*op, value, span)?)
+            }
+            ExprKind::Binary { op, lhs, rhs } => self.eval_binary(*op, lhs, rhs, span),
+            ExprKind::Assign { op, target, value } => {
+                let expected = self.binding_type(target);
+                let value = self.eval_with(value, expected)?;
+                self.assign(*op, target, value, span)?;
+                Ok(Value::Unit)
+            }
+            ExprKind::Block(block) => self.eval_block(block),
+            ExprKind::If { condition, then_branch, else_branch } => self.eval_if(condition, then_branch, else_branch.as_deref()),
+            ExprKind::While { label, condition, body } => self.eval_while(label, condition, body),
+            ExprKind::Loop { label, body } => self.eval_loop(label, body),
+            ExprKind::For { label, binding, iterable, body } => self.eval_for(label, binding, iterable, body),
+            ExprKind::Break { label, value } => {
+                let value = self.eval_optional(value.as_deref())?;
+                Err(Flow::Break { label: label.as_ref().map(|l| l.name.clone()), value, span })
+            }
+            ExprKind::Continue { label } => Err(Flow::Continue { label: label.as_ref().map(|l| l.name.clone()), span }),
+            ExprKind::Return(value) => Err(Flow::Return(self.eval_optional(value.as_deref())?)),
+            ExprKind::Call { callee, args } => self.eval_call(callee, args, span),
+            ExprKind::BuiltinCall { function, args } => {
+                let args = self.eval_args(args)?;
+                Ok(builtin(function, args, span)?)
+            }
+            ExprKind::MethodCall { receiver, method, args } if MATH_FUNCTIONS.contains(&method.name.as_str()) && args.is_empty() => {
+                let receiver = self.eval(receiver)?;
+                Ok(builtin(method, vec![receiver], span)?)
+            }
+            ExprKind::MacroCall { name, args } if name.name == "println" || name.name == "print" => self.eval_print(name, args, span),
+            kind => Err(unsupported_expr(kind, span).into()),
+        }
+    }
+
+    fn eval_with(&mut self, expr: &'a Expr, expected: Option<ValueType>) -> Eval {
+        match (&expr.kind, expected) {
+            (ExprKind::Literal(_) | ExprKind::Paren(_) | ExprKind::Unary { .. } | ExprKind::Binary { .. } | ExprKind::Block(_), Some(ValueType::Int(ty))) => {
+                self.eval_int(expr, ty)
+            }
+            _ => self.eval(expr),
+        }
+    }
+
+    fn eval_int(&mut self, expr: &'a Expr, ty: IntType) -> Eval {
+        let (span, expected) = (expr.span, Some(ValueType::Int(ty)));
+        match &expr.kind {
+            ExprKind::Literal(LiteralValue::Integer { value, negative, suffix: None }) => Ok(integer_literal(*value, *negative, Some(ty), span)?),
+            ExprKind::Paren(inner) => self.eval_with(inner, expected),
+            ExprKind::Unary { op: op @ (UnaryOp::Neg | UnaryOp::Not), operand } => {
+                let value = self.eval_with(operand, expected)?;
+                Ok(unary(*op, value, span)?)
+            }
+            ExprKind::Binary { op: op @ (BinaryOp::Shl | BinaryOp::Shr), lhs, rhs } => {
+                let lhs = self.eval_with(lhs, expected)?;
+                let rhs = self.eval(rhs)?;
+                Ok(binary(*op, lhs, rhs, span)?)
+            }
+            ExprKind::Binary { op, lhs, rhs } if !op.is_comparison() && !matches!(op, BinaryOp::And | BinaryOp::Or) => {
+                let lhs = self.eval_with(lhs, expected)?;
+                let rhs = self.eval_with(rhs, expected)?;
+                Ok(binary(*op, lhs, rhs, span)?)
+            }
+            ExprKind::Block(block) => self.eval_block_with(block, expected),
+            _ => self.eval(expr),
+        }
+    }
+
+    fn binding_type(&mut self, target: &Expr) -> Option<ValueType> {
+        match &target.kind {
+            ExprKind::Path(path) if path.segments.len() == 1 => self.lookup(&path.segments[0]).ok()?.ty,
+            ExprKind::Paren(inner) => self.binding_type(inner),
+            _ => None,
+        }
+    }
+
+    fn read(&mut self, name: &Ident) -> Result<Value, RuntimeError> {
+        let binding = self.lookup(name)?;
+        binding.value.clone().ok_or_else(|| RuntimeError::Uninitialized { span: name.span, name: name.name.clone() })
+    }
+
+    fn eval_optional(&mut self, expr: Option<&'a Expr>) -> Eval {
+        match expr {
+            Some(expr) => self.eval(expr),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn eval_binary(&mut self, op: BinaryOp, lhs: &'a Expr, rhs: &'a Expr, span: Span) -> Eval {
+        match op {
+            BinaryOp::And => Ok(Value::Bool(self.eval_bool(lhs)? && self.eval_bool(rhs)?)),
+            BinaryOp::Or => Ok(Value::Bool(self.eval_bool(lhs)? || self.eval_bool(rhs)?)),
+            _ => {
+                let lhs = self.eval(lhs)?;
+                let rhs = self.eval(rhs)?;
+                Ok(binary(op, lhs, rhs, span)?)
+            }
+        }
+    }
+
+    fn eval_if(&mut self, condition: &'a Expr, then_branch: &'a Block, else_branch: Option<&'a Expr>) -> Eval {
+        if self.eval_bool(condition)? {
+            self.eval_block(then_branch)
+        } else {
+            self.eval_optional(else_branch)
+        }
+    }
+
+    fn eval_while(&mut self, label: &Option<Ident>, condition: &'a Expr, body: &'a Block) -> Eval {
+        while self.eval_bool(condition)? {
+            if let Err(flow) = self.eval_block(body) {
+                if catch(label, flow)?.is_some() {
+                    break;
+                }
+            }
+        }
+        Ok(Value::Unit)
+    }
+
+    fn eval_loop(&mut self, label: &Option<Ident>, body: &'a Block) -> Eval {
+        loop {
+            if let Err(flow) = self.eval_block(body) {
+                if let Some(value) = catch(label, flow)? {
+                    return Ok(value);
+                }
+            }
+        }
+    }
+
+    fn eval_call(&mut self, callee: &'a Expr, args: &'a [Expr], span: Span) -> Eval {
+        let ExprKind::Path(path) = &callee.kind else {
+            return Err(unsupported("calling this expression", callee.span).into());
+        };
+        let names: Vec<&str> = path.segments.iter().map(|s| s.name.as_str()).collect();
+        if names == ["String", "new"] && args.is_empty() {
+            return Ok(Value::Str(String::new()));
+        }
+        let function = match names[..] {
+            [name] => self.functions.get(name).copied(),
+            _ => None,
+        };
+        let function = function.ok_or_else(|| RuntimeError::Undefined { span: path.span, name: path.to_string() })?;
+        let expected = function.params.iter().map(|param| param.ty.as_ref().and_then(ValueType::from_type)).chain(iter::repeat(None));
+        let args = args.iter().zip(expected).map(|(arg, ty)| self.eval_with(arg, ty)).collect::<Result<_, _>>()?;
+        Ok(self.call(function, args, span)?)
+    }
+
+    fn eval_print(&mut self, name: &Ident, args: &'a [Expr], span: Span) -> Eval {
+        let mut text = self.format(args, span)?;
+        if name.name == "println" {
+            text.push('\n');
+        }
+        self.out.write_all(text.as_bytes()).map_err(|e| RuntimeError::Output { span, message: e.to_string() })?;
+        Ok(Value::Unit)
+    }
+
+    fn eval_args(&mut self, args: &'a [Expr]) -> Result<Vec<Value>, Flow> {
+        args.iter().map(|arg| self.eval(arg)).collect()
+    }
+
+    fn eval_for(&mut self, label: &Option<Ident>, binding: &Ident, iterable: &'a Expr, body: &'a Block) -> Eval {
+        let ExprKind::Range { start: Some(start), end: Some(end), inclusive } = &iterable.kind else {
+            return Err(unsupported("iterating over anything but a bounded range", iterable.span).into());
+        };
+        let start = self.eval(start)?;
+        let end = self.eval(end)?;
+        let (Value::Int { value: first, ty }, Value::Int { value: last, .. }) = unify(start, end, iterable.span)? else {
+            return Err(RuntimeError::TypeMismatch { expected: "{integer}".to_string(), found: "range of non-integers".to_string(), span: iterable.span }.into());
+        };
+        let flip = if ty == Some(IntType::U128) { i128::MIN } else { 0 };
+        let (first, last) = (first ^ flip, last ^ flip);
+        let mut current = first;
+        while current < last || (*inclusive && current == last) {
+            self.scopes.push(vec![Binding { name: binding.name.clone(), value: Some(Value::Int { value: current ^ flip, ty }), ty: ty.map(ValueType::Int), mutable: false }]);
+            let result = self.eval_block(body);
+            self.scopes.pop();
+            if let Err(flow) = result {
+                if catch(label, flow)?.is_some() {
+                    break;
+                }
+            }
+            if current == last {
+                break;
+            }
+            current += 1;
+        }
+        Ok(Value::Unit)
+    }
+
+    fn assign(&mut self, op: Option<BinaryOp>, target: &'a Expr, value: Value, span: Span) -> Result<(), RuntimeError> {
+        let name = match &target.kind {
+            ExprKind::Path(path) if path.segments.len() == 1 => &path.segments[0],
+            ExprKind::Paren(inner) => return self.assign(op, inner, value, span),
+            _ => return Err(unsupported("assigning to this expression", target.span)),
+        };
+        let binding = self.lookup(name)?;
+        if binding.value.is_some() && !binding.mutable {
+            return Err(RuntimeError::Immutable { span, name: name.name.clone() });
+        }
+        let value = match (op, &binding.value) {
+            (Some(op), Some(current)) => binary(op, current.clone(), value, span)?,
+            (Some(_), None) => return Err(RuntimeError::Uninitialized { span: name.span, name: name.name.clone() }),
+            (None, _) => value,
+        };
+        let value = match binding.ty {
+            Some(ty) => coerce(value, ty, span)?,
+            None => value,
+        };
+        binding.ty = value.value_type();
+        binding.value = Some(value);
+        Ok(())
+    }
+
+    fn format(&mut self, args: &'a [Expr], span: Span) -> Result<String, Flow> {
+        let Some(Expr { kind: ExprKind::Literal(LiteralValue::Str(template)), .. }) = args.first() else {
+            return Err(RuntimeError::Format { span, reason: "the first argument must be a string literal".to_string() }.into());
+        };
+        let values = self.eval_args(&args[1..])?;
+        let error = |reason: &str| Flow::from(RuntimeError::Format { span, reason: reason.to_string() });
+
+        let mut text = String::new();
+        let mut used = vec![false; values.len()];
+        let mut next = 0;
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '}' {
+                if chars.next() != Some('}') {
+                    return Err(error("unmatched `}`"));
+                }
+                text.push('}');
+                continue;
+            }
+            if c != '{' {
+                text.push(c);
+                continue;
+            }
+            if chars.peek() == Some(&'{') {
+                chars.next();
+                text.push('{');
+                continue;
+            }
+            let mut placeholder = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(c) => placeholder.push(c),
+                    None => return Err(error("unterminated `{`")),
+                }
+            }
+            let (argument, spec) = placeholder.split_once(':').unwrap_or((&placeholder, ""));
+            let value = if argument.is_empty() || argument.bytes().all(|b| b.is_ascii_digit()) {
+                let index = if argument.is_empty() {
+                    next += 1;
+                    next - 1
+                } else {
+                    argument.parse().unwrap()
+                };
+                let value = values.get(index).ok_or_else(|| error(&format!("missing argument {}", index)))?;
+                used[index] = true;
+                value.clone()
+            } else {
+                self.read(&Ident { name: argument.to_string(), span })?
+            };
+            let precision = spec.strip_prefix('.').and_then(|digits| digits.parse::<usize>().ok());
+            match (spec, precision, &value) {
+                ("", _, _) => text.push_str(&value.to_string()),
+                ("?", _, _) => text.push_str(&value.debug()),
+                (_, Some(precision), Value::Float { value, .. }) => text.push_str(&format!("{:.*}", precision, value)),
+                (_, Some(precision), Value::Str(s)) => text.extend(s.chars().take(precision)),
+                (_, Some(_), _) => text.push_str(&value.to_string()),
+                _ => return Err(error(&format!("unsupported format spec `{}`", spec))),
+            }
+        }
+        if let Some(index) = used.iter().position(|used| !used) {
+            return Err(error(&format!("argument {} is never used", index)));
+        }
+        Ok(text)
+    }
+}
+
+fn catch(label: &Option<Ident>, flow: Flow) -> Result<Option<Value>, Flow> {
+    let matches = |target: &Option<String>| target.as_ref().is_none_or(|target| label.as_ref().is_some_and(|label| label.name == *target));
+    match flow {
+        Flow::Break { label: target, value, .. } if matches(&target) => Ok(Some(value)),
+        Flow::Continue { label: target, .. } if matches(&target) => Ok(None),
+        flow => Err(flow),
+    }
+}
+
+fn unsupported(what: &str, span: Span) -> RuntimeError {
+    RuntimeError::Unsupported { span, what: what.to_string() }
+}
+
+fn unsupported_expr(kind: &ExprKind, span: Span) -> RuntimeError {
+    let what = match kind {
+        ExprKind::MacroCall { name, .. } => format!("macro `{}!`", name.name),
+        ExprKind::MethodCall { method, .. } => format!("method `{}`", method.name),
+        ExprKind::Path(_) => "a multi-segment path".to_string(),
+        ExprKind::Range { .. } => "a range outside of a `for` loop".to_string(),
+        ExprKind::Field { .. } => "field access".to_string(),
+        ExprKind::Index { .. } => "indexing".to_string(),
+        ExprKind::Try(_) => "the `?` operator".to_string(),
+        ExprKind::StructLiteral { .. } => "a struct literal".to_string(),
+        ExprKind::Array(_) => "an array".to_string(),
+        ExprKind::Tuple(_) => "a tuple".to_string(),
+        _ => "an invalid expression".to_string(),
+    };
+    RuntimeError::Unsupported { span, what }
+}
+
+fn type_mismatch(expected: &str, found: &Value, span: Span) -> RuntimeError {
+    RuntimeError::TypeMismatch { span, expected: expected.to_string(), found: found.type_name() }
+}
+
+fn declared_type(ty: &Type) -> Result<ValueType, RuntimeError> {
+    ValueType::from_type(ty).ok_or_else(|| unsupported(&format!("type `{}`", ty), ty.span))
+}
+
+fn literal_value(literal: &LiteralValue, span: Span) -> Result<Value, RuntimeError> {
+    let value = match literal {
+        LiteralValue::Integer { value, negative, suffix: None } => integer_literal(*value, *negative, None, span)?,
+        LiteralValue::Integer { value, negative, suffix: Some(suffix) } => {
+            let ty = IntType::from_name(suffix).ok_or_else(|| unsupported(&format!("type `{}`", suffix), span))?;
+            integer_literal(*value, *negative, Some(ty), span)?
+        }
+        LiteralValue::Float { value, suffix: None } => Value::Float { value: *value, ty: None },
+        LiteralValue::Float { value, suffix: Some(suffix) } => {
+            let ty = FloatType::from_name(suffix).ok_or_else(|| unsupported(&format!("type `{}`", suffix), span))?;
+            Value::Float { value: ty.round(*value), ty: Some(ty) }
+        }
+        LiteralValue::Str(s) => Value::Str(s.clone()),
+        LiteralValue::Char(c) => Value::Char(*c),
+        LiteralValue::Byte(b) => Value::Int { value: *b as i128, ty: Some(IntType::U8) },
+        LiteralValue::ByteStr(_) => return Err(unsupported("a byte string", span)),
+    };
+    Ok(value)
+}
+
+fn integer_literal(value: u128, negative: bool, ty: Option<IntType>, span: Span) -> Result<Value, RuntimeError> {
+    let signed = if negative { 0i128.checked_sub_unsigned(value) } else { i128::try_from(value).ok() };
+    match (signed, ty) {
+        (_, Some(IntType::U128)) if !negative => Ok(Value::Int { value: value as i128, ty }),
+        (Some(value), Some(ty)) => coerce(Value::Int { value, ty: None }, ValueType::Int(ty), span),
+        (Some(value), None) => Ok(Value::Int { value, ty: None }),
+        (None, ty) => {
+            let value = format!("{}{}", if negative { "-" } else { "" }, value);
+            Err(RuntimeError::OutOfRange { span, value, ty: ty.unwrap_or(IntType::I32).name().to_string() })
+        }
+    }
+}
+
+fn coerce(value: Value, ty: ValueType, span: Span) -> Result<Value, RuntimeError> {
+    match (value, ty) {
+        (Value::Int { value, ty: None }, ValueType::Int(target)) if target.contains(value) => Ok(Value::Int { value, ty: Some(target) }),
+        (Value::Int { value, ty: None }, ValueType::Int(target)) => {
+            Err(RuntimeError::OutOfRange { span, value: value.to_string(), ty: target.name().to_string() })
+        }
+        (Value::Float { value, ty: None }, ValueType::Float(target)) => Ok(Value::Float { value: target.round(value), ty: Some(target) }),
+        (value, ty) if value.value_type() == Some(ty) => Ok(value),
+        (value, ty) => Err(type_mismatch(&ty.to_string(), &value, span)),
+    }
+}
+
+fn unify(lhs: Value, rhs: Value, span: Span) -> Result<(Value, Value), RuntimeError> {
+    match (lhs.value_type(), rhs.value_type()) {
+        (None, Some(ty)) => Ok((coerce(lhs, ty, span)?, rhs)),
+        (Some(ty), None) => {
+            let rhs = coerce(rhs, ty, span)?;
+            Ok((lhs, rhs))
+        }
+        (None, None) if mem::discriminant(&lhs) != mem::discriminant(&rhs) => Err(type_mismatch(&lhs.type_name(), &rhs, span)),
+        (Some(a), Some(b)) if a != b => Err(type_mismatch(&a.to_string(), &rhs, span)),
+        _ => Ok((lhs, rhs)),
+    }
+}
+
+fn checked_int(value: Option<i128>, ty: Option<IntType>, op: &str, span: Span) -> Result<Value, RuntimeError> {
+    let target = ty.unwrap_or(IntType::I32);
+    match value {
+        Some(value) if target == IntType::U128 || target.contains(value) => Ok(Value::Int { value, ty }),
+        _ => Err(RuntimeError::Overflow { span, op: op.to_string(), ty: target.name().to_string() }),
+    }
+}
+
+fn default_int(value: &Value, span: Span) -> Result<(), RuntimeError> {
+    match value {
+        Value::Int { value, ty: None } if !IntType::I32.contains(*value) => {
+            Err(RuntimeError::OutOfRange { span, value: value.to_string(), ty: IntType::I32.name().to_string() })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn unary(op: UnaryOp, value: Value, span: Span) -> Result<Value, RuntimeError> {
+    let invalid = |symbol: &str, value: &Value| RuntimeError::InvalidOperand { span, op: symbol.to_string(), ty: value.type_name() };
+    default_int(&value, span)?;
+    match (op, value) {
+        (UnaryOp::Neg, Value::Int { value, ty }) if ty.is_none_or(IntType::is_signed) => checked_int(value.checked_neg(), ty, "negate", span),
+        (UnaryOp::Neg, Value::Float { value, ty }) => Ok(Value::Float { value: -value, ty }),
+        (UnaryOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+        (UnaryOp::Not, Value::Int { value, ty: Some(ty) }) if !ty.is_signed() && ty != IntType::U128 => Ok(Value::Int { value: ty.max() - value, ty: Some(ty) }),
+        (UnaryOp::Not, Value::Int { value, ty }) => Ok(Value::Int { value: !value, ty }),
+        (UnaryOp::Neg, value) => Err(invalid("-", &value)),
+        (UnaryOp::Not, value) => Err(invalid("!", &value)),
+        (UnaryOp::Deref | UnaryOp::Ref | UnaryOp::RefMut, _) => Err(unsupported("a reference", span)),
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Value, rhs: Value, span: Span) -> Result<Value, RuntimeError> {
+    if let (BinaryOp::Shl | BinaryOp::Shr, Value::Int { value, ty }, Value::Int { value: amount, .. }) = (op, &lhs, &rhs) {
+        default_int(&lhs, span)?;
+        let target = ty.unwrap_or(IntType::I32);
+        let verb = if op == BinaryOp::Shl { "shift left" } else { "shift right" };
+        if !(0..target.bits() as i128).contains(amount) {
+            return Err(RuntimeError::Overflow { span, op: verb.to_string(), ty: target.name().to_string() });
+        }
+        let unused = 128 - target.bits();
+        let result = match op {
+            BinaryOp::Shl => value << amount,
+            _ if target == IntType::U128 => ((*value as u128) >> amount) as i128,
+            _ => value >> amount,
+        };
+        let result = if target.is_signed() || target == IntType::U128 { (result << unused) >> unused } else { result & target.max() };
+        return Ok(Value::Int { value: result, ty: *ty });
+    }
+
+    let (lhs, rhs) = unify(lhs, rhs, span)?;
+    default_int(&lhs, span)?;
+    default_int(&rhs, span)?;
+    let invalid = || RuntimeError::InvalidOperand { span, op: op.symbol().to_string(), ty: lhs.type_name() };
+    if op.is_comparison() {
+        let ordering = match (&lhs, &rhs) {
+            (Value::Int { value: a, ty: Some(IntType::U128) }, Value::Int { value: b, .. }) => (*a as u128).partial_cmp(&(*b as u128)),
+            (Value::Int { value: a, .. }, Value::Int { value: b, .. }) => a.partial_cmp(b),
+            (Value::Float { value: a, .. }, Value::Float { value: b, .. }) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.partial_cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.partial_cmp(b),
+            (Value::Unit, Value::Unit) => Some(std::cmp::Ordering::Equal),
+            _ => return Err(invalid()),
+        };
+        let result = match op {
+            BinaryOp::Eq => ordering.is_some_and(|o| o.is_eq()),
+            BinaryOp::Ne => !ordering.is_some_and(|o| o.is_eq()),
+            BinaryOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+            BinaryOp::Le => ordering.is_some_and(|o| o.is_le()),
+            BinaryOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+            _ => ordering.is_some_and(|o| o.is_ge()),
+        };
+        return Ok(Value::Bool(result));
+    }
+
+    match (&lhs, &rhs) {
+        (Value::Int { value: a, ty }, Value::Int { value: b, .. }) => {
+            if matches!(op, BinaryOp::Div | BinaryOp::Rem) && *b == 0 {
+                return Err(RuntimeError::DivisionByZero { span });
+            }
+            let wide = *ty == Some(IntType::U128);
+            let unsigned = |f: fn(u128, u128) -> Option<u128>| f(*a as u128, *b as u128).map(|v| v as i128);
+            let (verb, result) = match op {
+                BinaryOp::Add if wide => ("add", unsigned(u128::checked_add)),
+                BinaryOp::Sub if wide => ("subtract", unsigned(u128::checked_sub)),
+                BinaryOp::Mul if wide => ("multiply", unsigned(u128::checked_mul)),
+                BinaryOp::Div if wide => ("divide", unsigned(u128::checked_div)),
+                BinaryOp::Rem if wide => ("calculate the remainder", unsigned(u128::checked_rem)),
+                BinaryOp::Add => ("add", a.checked_add(*b)),
+                BinaryOp::Sub => ("subtract", a.checked_sub(*b)),
+                BinaryOp::Mul => ("multiply", a.checked_mul(*b)),
+                BinaryOp::Div => ("divide", a.checked_div(*b)),
+                BinaryOp::Rem => ("calculate the remainder", a.checked_rem(*b)),
+                BinaryOp::BitAnd => ("and", Some(a & b)),
+                BinaryOp::BitOr => ("or", Some(a | b)),
+                BinaryOp::BitXor => ("xor", Some(a ^ b)),
+                _ => return Err(invalid()),
+            };
+            checked_int(result, *ty, verb, span)
+        }
+        (Value::Float { value: a, ty }, Value::Float { value: b, .. }) => {
+            let result = match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                BinaryOp::Div => a / b,
+                BinaryOp::Rem => a % b,
+                _ => return Err(invalid()),
+            };
+            Ok(Value::Float { value: ty.map_or(result, |ty| ty.round(result)), ty: *ty })
+        }
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            BinaryOp::BitAnd => Ok(Value::Bool(a & b)),
+            BinaryOp::BitOr => Ok(Value::Bool(a | b)),
+            BinaryOp::BitXor => Ok(Value::Bool(a ^ b)),
+            _ => Err(invalid()),
+        },
+        (Value::Str(a), Value::Str(b)) if op == BinaryOp::Add => Ok(Value::Str(format!("{}{}", a, b))),
+        _ => Err(invalid()),
+    }
+}
+
+fn builtin(function: &Ident, args: Vec<Value>, span: Span) -> Result<Value, RuntimeError> {
+    let expected = if function.name == "new" { 0 } else { 1 };
+    if args.len() != expected {
+        return Err(RuntimeError::ArgumentCount { span, name: function.name.clone(), expected, found: args.len() });
+    }
+    if function.name == "new" {
+        return Ok(Value::Str(String::new()));
+    }
+    let (value, ty) = match &args[0] {
+        Value::Float { value, ty } => (*value, *ty),
+        value => return Err(type_mismatch("{float}", value, span)),
+    };
+    let result = match function.name.as_str() {
+        "sin" => value.sin(),
+        "cos" => value.cos(),
+        "tan" => value.tan(),
+        "ctan" => 1.0 / value.tan(),
+        "sqrt" => value.sqrt(),
+        name => return Err(unsupported(&format!("built-in `{}`", name), function.span)),
+    };
+    Ok(Value::Float { value: ty.map_or(result, |ty| ty.round(result)), ty })
+}
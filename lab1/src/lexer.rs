@@ -0,0 +1,740 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::mem;
+use unicode_normalization::UnicodeNormalization;
+use unicode_xid::UnicodeXID;
+use crate::config::{Lexicon, DEFAULT_LEXICON};
+use crate::confusables::{mixed_script_confusables, Skeletons};
+use crate::error::LexError;
+use crate::operators::OPERATORS;
+use crate::token::{LiteralValue, Position, Span, Token, TokenType};
+use crate::token::TokenType::{CharLiteral, Comment, Delimiter, Identifier, Invalid, Newline, StringLiteral, Whitespace};
+
+pub const KEY_WORDS: [&str; 16] = ["if", "else", "let", "for", "loop", "while", "break", "continue", "return", "mut", "enum", "struct", "fn", "impl", "use", "macro"];
+pub const IDENTIFIER_SYMBOLS: &str = "^[shvachkaSHVACHKAшвачкаШВАЧКА0-9_]+$";
+pub const FUNCTIONS: [&str; 6] = ["sin", "cos", "tan", "ctan", "sqrt", "new"];
+pub const COMPARISON_SYMBOLS: [&str; 6] = ["<", "<=", ">=", ">", "==", "!="];
+pub const ARITHMETIC_SYMBOLS: [&str; 9] = ["-", "+", "*", "/", "%", "^", "|", "<<", ">>"];
+pub const DELIMITER_SYMBOLS: [&str; 22] = [";", ":", "::", ",", "[", "]", "(", ")", "{", "}", "?", "\'", "\"", "&", ".", "..", "...", "..=", "->", "=>", "@", "_"];
+pub const LOGICAL_SYMBOLS: [&str; 3] = ["&&", "||", "!"];
+pub const ASSIGNMENT_SYMBOLS: [&str; 11] = ["=", "/=", "*=", "+=", "-=", "%=", "&=", "|=", "^=", "<<=", ">>="];
+pub const ATTRIBUTE_SYMBOLS: [&str; 2] = ["#", "#!"];
+pub const DATA_TYPES: [&str; 18] = ["char", "String", "str", "bool", "f32", "f64", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+pub(crate) const LETTER_CLASS: &str = "[a-zA-Zа-яА-ЯіІїЇєЄґҐ]";
+pub(crate) const WORD_CLASS: &str = "[a-zA-Zа-яА-ЯіІїЇєЄґҐ0-9_]";
+
+const STRING_START: char = '"';
+const CHAR_START: char = '\'';
+const COMMENT_START: char = '/';
+const MACRO_BANG: char = '!';
+
+lazy_static! {
+    static ref WHITESPACE_REGEX: Regex = Regex::new("\\s").unwrap();
+    static ref NUMBER_REGEX: Regex = Regex::new("[0-9]+").unwrap();
+    static ref LITERAL_START_REGEX: Regex = Regex::new(&format!("{}+", LETTER_CLASS)).unwrap();
+    static ref LITERAL_REGEX: Regex = Regex::new(&format!("{}+", WORD_CLASS)).unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberKind {
+    Unsigned(u32),
+    Signed(u32),
+    Float,
+}
+
+pub(crate) fn numeric_type(lexicon: &Lexicon, name: &str) -> Option<NumberKind> {
+    if !lexicon.is_data_type(name) {
+        return None;
+    }
+    let mut chars = name.chars();
+    let kind = chars.next()?;
+    let bits = match chars.as_str() {
+        "size" => usize::BITS,
+        digits => digits.parse().ok().filter(|bits| *bits > 0)?,
+    };
+    match kind {
+        'u' => Some(NumberKind::Unsigned(bits)),
+        'i' => Some(NumberKind::Signed(bits)),
+        'f' => Some(NumberKind::Float),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentifierMode {
+    #[default]
+    Restricted,
+    Unicode,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexerOptions {
+    pub keep_comments: bool,
+    pub identifiers: IdentifierMode,
+    pub trivia: bool,
+}
+
+pub struct Lexer<'a> {
+    input: &'a str,
+    lexicon: &'a Lexicon,
+    options: LexerOptions,
+    pos: usize,
+    line: usize,
+    column: usize,
+    skeletons: Skeletons,
+    warnings: Vec<LexError>,
+    reach: Cell<usize>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::with_options(input, LexerOptions::default())
+    }
+
+    pub fn with_options(input: &'a str, options: LexerOptions) -> Self {
+        Self::with_lexicon(input, &DEFAULT_LEXICON, options)
+    }
+
+    pub fn with_lexicon(input: &'a str, lexicon: &'a Lexicon, options: LexerOptions) -> Self {
+        Lexer {
+            input,
+            lexicon,
+            options,
+            pos: 0,
+            line: 1,
+            column: 1,
+            skeletons: Skeletons::default(),
+            warnings: Vec::new(),
+            reach: Cell::new(0),
+        }
+    }
+
+    pub fn take_warnings(&mut self) -> Vec<LexError> {
+        mem::take(&mut self.warnings)
+    }
+
+    pub(crate) fn reach(&self) -> usize {
+        self.reach.get()
+    }
+
+    fn touch(&self, count: usize) {
+        let end = self.input[self.pos..]
+            .char_indices()
+            .nth(count - 1)
+            .map_or(self.input.len() + 1, |(offset, c)| self.pos + offset + c.len_utf8());
+        self.extend_reach(end);
+    }
+
+    fn extend_reach(&self, end: usize) {
+        if end > self.reach.get() {
+            self.reach.set(end);
+        }
+    }
+
+    fn is_word_start(&self, c: char) -> bool {
+        match self.options.identifiers {
+            IdentifierMode::Restricted => is_match_char(&LITERAL_START_REGEX, c),
+            IdentifierMode::Unicode => c.is_xid_start(),
+        }
+    }
+
+    fn is_word_continue(&self, c: char) -> bool {
+        match self.options.identifiers {
+            IdentifierMode::Restricted => is_match_char(&LITERAL_REGEX, c),
+            IdentifierMode::Unicode => c.is_xid_continue(),
+        }
+    }
+
+    pub(crate) fn location(&self) -> Position {
+        Position { offset: self.pos, line: self.line, column: self.column }
+    }
+
+    fn current(&self) -> Option<char> {
+        let current = self.input[self.pos..].chars().next();
+        self.extend_reach(current.map_or(self.input.len() + 1, |c| self.pos + c.len_utf8()));
+        current
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.touch(2);
+        self.input[self.pos..].chars().nth(1)
+    }
+
+    pub(crate) fn restore(&mut self, position: Position) {
+        self.pos = position.offset;
+        self.line = position.line;
+        self.column = position.column;
+    }
+
+    fn bump_if(&mut self, predicate: impl Fn(char) -> bool) -> Option<char> {
+        if self.current().is_some_and(predicate) {
+            self.bump()
+        } else {
+            None
+        }
+    }
+
+    fn previous(&self) -> Option<char> {
+        self.input[..self.pos].chars().next_back()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.current()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Option<Result<Token, LexError>> {
+        while let Some(current) = self.current() {
+            let start = self.location();
+            self.reach.set(start.offset);
+            let result = if is_match_char(&WHITESPACE_REGEX, current) {
+                if !self.options.trivia {
+                    self.bump();
+                    continue;
+                }
+                Ok(self.get_whitespace_token())
+            } else if current == COMMENT_START && matches!(self.peek_next(), Some('/') | Some('*')) {
+                self.get_comment_token()
+            } else if let Some(result) = self.get_prefixed_literal_token() {
+                result
+            } else if current == STRING_START {
+                self.get_string_literal_token(start.offset, false)
+            } else if current == CHAR_START {
+                self.get_char_literal_token(start.offset, false).unwrap_or_else(|| Ok(self.get_lifetime_token(start.offset)))
+            } else if self.is_word_start(current) || current == '_' && self.peek_next().is_some_and(|c| self.is_word_continue(c)) {
+                self.get_literal_token()
+            } else if is_match_char(&NUMBER_REGEX, current) {
+                self.get_numeric_token(start.offset)
+            } else {
+                self.get_operator_or_numeric_token()
+            };
+
+            let span = Span::new(start, self.location());
+            match result {
+                Ok(token) if token.type_name == Comment && !self.options.keep_comments && !self.options.trivia => continue,
+                Ok(mut token) => {
+                    token.span = span;
+                    if self.options.trivia && token.content != self.input[span.start..span.end] {
+                        token.content = self.input[span.start..span.end].to_string();
+                    }
+                    return Some(Ok(token));
+                }
+                Err(error) => return Some(Err(error.with_span(span))),
+            }
+        }
+
+        None
+    }
+
+    fn get_whitespace_token(&mut self) -> Token {
+        let start = self.pos;
+        if self.current() == Some('\r') && self.peek_next() == Some('\n') {
+            self.bump();
+        }
+        if self.bump_if(|c| c == '\n').is_some() {
+            return Token::new(Newline, self.input[start..self.pos].to_string());
+        }
+        while self.current().is_some_and(|c| c != '\n' && is_match_char(&WHITESPACE_REGEX, c))
+            && !(self.current() == Some('\r') && self.peek_next() == Some('\n')) {
+            self.bump();
+        }
+        Token::new(Whitespace, self.input[start..self.pos].to_string())
+    }
+
+    fn get_comment_token(&mut self) -> Result<Token, LexError> {
+        let start = self.pos;
+        self.bump();
+        if self.bump() == Some('/') {
+            while self.current().is_some_and(|c| c != '\n') {
+                self.bump();
+            }
+            return Ok(Token::new(Comment, self.input[start..self.pos].to_string()));
+        }
+
+        let mut depth = 1;
+        while depth > 0 {
+            match (self.bump(), self.current()) {
+                (Some('/'), Some('*')) => {
+                    self.bump();
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    self.bump();
+                    depth -= 1;
+                }
+                (Some(_), _) => {}
+                (None, _) => return Err(LexError::UnterminatedComment { span: Span::default() }),
+            }
+        }
+
+        Ok(Token::new(Comment, self.input[start..self.pos].to_string()))
+    }
+
+    fn get_prefixed_literal_token(&mut self) -> Option<Result<Token, LexError>> {
+        if !matches!(self.current(), Some('b') | Some('r')) {
+            return None;
+        }
+        let start = self.location();
+        let byte = self.bump_if(|c| c == 'b').is_some();
+        let token = match self.current() {
+            Some('r') => self.get_raw_string_literal_token(start.offset, byte),
+            Some(STRING_START) if byte => Some(self.get_string_literal_token(start.offset, true)),
+            Some(CHAR_START) if byte => self.get_char_literal_token(start.offset, true),
+            _ => None,
+        };
+        if token.is_none() {
+            self.restore(start);
+        }
+        token
+    }
+
+    fn get_string_literal_token(&mut self, start: usize, byte: bool) -> Result<Token, LexError> {
+        self.bump();
+        let mut value = Vec::new();
+        let mut error = None;
+
+        loop {
+            match self.bump() {
+                None => return Err(LexError::UnterminatedString { span: Span::default() }),
+                Some(STRING_START) => break,
+                Some('\\') if matches!(self.current(), Some('\n') | Some('\r')) => {
+                    while self.bump_if(char::is_whitespace).is_some() {}
+                }
+                Some('\\') => match self.scan_escape(byte) {
+                    Ok(v) => value.push(v),
+                    Err(reason) => {
+                        error.get_or_insert(reason);
+                    }
+                },
+                Some(c) if byte && !c.is_ascii() => {
+                    error.get_or_insert(format!("non-ASCII character `{}` in byte string", c));
+                }
+                Some(c) => value.push(c as u32),
+            }
+        }
+
+        if let Some(reason) = error {
+            return Err(LexError::BadStringLiteral { span: Span::default(), reason });
+        }
+        let content = self.input[start..self.pos].to_string();
+        let value = if byte {
+            LiteralValue::ByteStr(value.into_iter().map(|v| v as u8).collect())
+        } else {
+            LiteralValue::Str(value.into_iter().filter_map(char::from_u32).collect())
+        };
+        Ok(Token::literal(StringLiteral, content, value))
+    }
+
+    fn get_raw_string_literal_token(&mut self, start: usize, byte: bool) -> Option<Result<Token, LexError>> {
+        self.bump();
+        let mut hashes = 0;
+        while self.bump_if(|c| c == '#').is_some() {
+            hashes += 1;
+        }
+        self.bump_if(|c| c == STRING_START)?;
+        let body_start = self.pos;
+
+        loop {
+            match self.bump() {
+                None => return Some(Err(LexError::UnterminatedString { span: Span::default() })),
+                Some(STRING_START) => {
+                    let body_end = self.pos - 1;
+                    if hashes > 0 {
+                        self.touch(hashes);
+                    }
+                    if self.input[self.pos..].chars().take(hashes).filter(|c| *c == '#').count() == hashes {
+                        for _ in 0..hashes {
+                            self.bump();
+                        }
+                        let content = self.input[start..self.pos].to_string();
+                        let body = &self.input[body_start..body_end];
+                        if byte && !body.is_ascii() {
+                            let reason = "non-ASCII character in raw byte string".to_string();
+                            return Some(Err(LexError::BadStringLiteral { span: Span::default(), reason }));
+                        }
+                        let value = if byte {
+                            LiteralValue::ByteStr(body.as_bytes().to_vec())
+                        } else {
+                            LiteralValue::Str(body.to_string())
+                        };
+                        return Some(Ok(Token::literal(StringLiteral, content, value)));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn get_char_literal_token(&mut self, start: usize, byte: bool) -> Option<Result<Token, LexError>> {
+        let checkpoint = self.location();
+        self.bump();
+
+        let value = match self.bump() {
+            Some('\\') => self.scan_escape(byte),
+            Some(CHAR_START) | None => {
+                self.restore(checkpoint);
+                return None;
+            }
+            Some(c) if byte && !c.is_ascii() => Err(format!("non-ASCII character `{}` in byte literal", c)),
+            Some(c) => Ok(c as u32),
+        };
+        if self.bump_if(|c| c == CHAR_START).is_none() {
+            self.restore(checkpoint);
+            return None;
+        }
+
+        let content = self.input[start..self.pos].to_string();
+        let result = match value {
+            Ok(v) if byte => Ok(Token::literal(CharLiteral, content, LiteralValue::Byte(v as u8))),
+            Ok(v) => Ok(Token::literal(CharLiteral, content, LiteralValue::Char(char::from_u32(v).unwrap()))),
+            Err(reason) => Err(LexError::BadCharLiteral { span: Span::default(), reason }),
+        };
+        Some(result)
+    }
+
+    fn get_lifetime_token(&mut self, start: usize) -> Token {
+        self.bump();
+        if !self.current().is_some_and(|c| c == '_' || self.is_word_start(c)) {
+            return Token::new(Delimiter, CHAR_START.to_string());
+        }
+        while self.current().is_some_and(|c| self.is_word_continue(c)) {
+            self.bump();
+        }
+        Token::new(TokenType::Lifetime, self.input[start..self.pos].to_string())
+    }
+
+    fn scan_escape(&mut self, byte: bool) -> Result<u32, String> {
+        match self.bump() {
+            Some('n') => Ok('\n' as u32),
+            Some('r') => Ok('\r' as u32),
+            Some('t') => Ok('\t' as u32),
+            Some('\\') => Ok('\\' as u32),
+            Some('0') => Ok(0),
+            Some('\'') => Ok('\'' as u32),
+            Some('"') => Ok('"' as u32),
+            Some('x') => {
+                let digits: String = (0..2).filter_map(|_| self.bump_if(|c| c.is_ascii_hexdigit())).collect();
+                if digits.len() != 2 {
+                    return Err("numeric character escape is too short".to_string());
+                }
+                let value = u32::from_str_radix(&digits, 16).unwrap();
+                if !byte && value > 0x7F {
+                    return Err(format!("out of range hex escape `\\x{}`", digits));
+                }
+                Ok(value)
+            }
+            Some('u') if byte => Err("unicode escape in byte literal".to_string()),
+            Some('u') => {
+                if self.bump_if(|c| c == '{').is_none() {
+                    return Err("incorrect unicode escape sequence".to_string());
+                }
+                let mut digits = String::new();
+                while let Some(c) = self.bump_if(|c| c.is_ascii_hexdigit() || c == '_') {
+                    if c != '_' {
+                        digits.push(c);
+                    }
+                }
+                if self.bump_if(|c| c == '}').is_none() {
+                    return Err("unterminated unicode escape".to_string());
+                }
+                if digits.is_empty() || digits.len() > 6 {
+                    return Err("unicode escape must have between 1 and 6 hex digits".to_string());
+                }
+                let value = u32::from_str_radix(&digits, 16).unwrap();
+                char::from_u32(value)
+                    .map(|_| value)
+                    .ok_or_else(|| format!("invalid unicode character escape `\\u{{{}}}`", digits))
+            }
+            Some(c) => Err(format!("unknown character escape `\\{}`", c)),
+            None => Err("unterminated escape sequence".to_string()),
+        }
+    }
+
+    fn get_operator_or_numeric_token(&mut self) -> Result<Token, LexError> {
+        let start = self.pos;
+        let symbol = self.current().unwrap();
+        let plus_or_minus = symbol == '+' || symbol == '-';
+        if plus_or_minus && self.peek_next().is_some_and(|c| is_match_char(&NUMBER_REGEX, c))
+            && !self.previous().is_some_and(|c| is_match_char(&NUMBER_REGEX, c)) {
+            self.bump();
+            return self.get_numeric_token(start);
+        }
+        Ok(self.get_operator_token())
+    }
+
+    fn get_operator_token(&mut self) -> Token {
+        let (longest, reach) = OPERATORS.longest_match_with_reach(&self.input[self.pos..]);
+        self.extend_reach(self.pos + reach);
+        match longest {
+            Some((length, token_type)) => {
+                let operator = self.input[self.pos..self.pos + length].to_string();
+                for _ in operator.chars() {
+                    self.bump();
+                }
+                Token::new(token_type, operator)
+            }
+            None => Token::new(Invalid, self.bump().unwrap().to_string()),
+        }
+    }
+
+    fn get_literal_token(&mut self) -> Result<Token, LexError> {
+        let start_location = self.location();
+        let start = self.pos;
+        while self.current().is_some_and(|c| self.is_word_continue(c)) {
+            self.bump();
+        }
+        let literal: String = match self.options.identifiers {
+            IdentifierMode::Restricted => self.input[start..self.pos].to_string(),
+            IdentifierMode::Unicode => self.input[start..self.pos].nfc().collect(),
+        };
+
+        if literal == "true" || literal == "false" {
+            return Ok(Token::new(TokenType::Boolean, literal));
+        }
+
+        if self.lexicon.key_words.is_match(&literal) {
+            return Ok(Token::new(TokenType::KeyWord, literal));
+        }
+
+        if self.current() == Some(MACRO_BANG) && self.peek_next() != Some('=') {
+            self.bump();
+            return Ok(Token::new(TokenType::Macro, self.input[start..self.pos].to_string()));
+        }
+
+        if self.lexicon.data_types.is_match(&literal) {
+            return Ok(Token::new(TokenType::DataType, literal));
+        }
+
+        if self.lexicon.functions.is_match(&literal) {
+            return Ok(Token::new(TokenType::Function, literal));
+        }
+
+        if self.options.identifiers == IdentifierMode::Unicode || self.lexicon.identifier.is_match(&literal) {
+            self.check_confusables(&literal, Span::new(start_location, self.location()));
+            return Ok(Token::new(Identifier, literal));
+        }
+
+        let character = literal
+            .chars()
+            .find(|c| !is_match_char(&self.lexicon.identifier, *c))
+            .unwrap_or_else(|| literal.chars().next().unwrap());
+        Err(LexError::IllegalIdentifierChar { span: Span::default(), character })
+    }
+
+    fn check_confusables(&mut self, identifier: &str, span: Span) {
+        for (character, lookalike) in mixed_script_confusables(identifier) {
+            self.warnings.push(LexError::ConfusableCharacter { span, character, lookalike });
+        }
+        if let Some(other) = self.skeletons.confusable_with(identifier) {
+            self.warnings.push(LexError::ConfusableIdentifier { span, name: identifier.to_string(), other });
+        }
+    }
+
+    fn get_numeric_token(&mut self, start: usize) -> Result<Token, LexError> {
+        self.scan_number(start).map_err(|reason| LexError::BadNumber { span: Span::default(), reason })
+    }
+
+    fn scan_number(&mut self, start: usize) -> Result<Token, String> {
+        let negative = self.input[start..].starts_with('-');
+        let radix = match (self.current(), self.peek_next()) {
+            (Some('0'), Some('x')) => 16,
+            (Some('0'), Some('o')) => 8,
+            (Some('0'), Some('b')) => 2,
+            _ => 10,
+        };
+        let mut is_float = false;
+
+        if radix != 10 {
+            self.bump();
+            self.bump();
+        }
+        let digits_start = self.pos;
+        if radix == 16 {
+            while self.bump_if(|c| c.is_ascii_hexdigit() || c == '_').is_some() {}
+        } else {
+            self.eat_decimal_digits();
+        }
+
+        if radix == 10 {
+            if self.current() == Some('.')
+                && !self.peek_next().is_some_and(|c| c == '.' || c == '_' || c.is_alphabetic()) {
+                is_float = true;
+                self.bump();
+                self.eat_decimal_digits();
+                if self.current() == Some('.') && self.peek_next().is_some_and(|c| c.is_ascii_digit()) {
+                    while self.bump_if(|c| c.is_ascii_digit() || c == '.' || c == '_').is_some() {}
+                    return Err("number has more than one decimal point".to_string());
+                }
+            }
+            if self.has_exponent() {
+                is_float = true;
+                self.bump();
+                self.bump_if(|c| c == '+' || c == '-');
+                self.eat_decimal_digits();
+            }
+        }
+        let digits_end = self.pos;
+
+        while self.bump_if(|c| c.is_alphanumeric() || c == '_').is_some() {}
+        let content = self.input[start..self.pos].to_string();
+        let suffix = &self.input[digits_end..self.pos];
+        let digits: String = self.input[digits_start..digits_end].chars().filter(|c| *c != '_').collect();
+
+        if digits.is_empty() {
+            return Err("missing digits after the integer base prefix".to_string());
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_digit(radix) && *c != '.' && radix != 10) {
+            return Err(format!("invalid digit `{}` in base {} literal", c, radix));
+        }
+
+        let kind = if suffix.is_empty() {
+            None
+        } else {
+            match numeric_type(self.lexicon, suffix) {
+                Some(NumberKind::Float) if radix != 10 => {
+                    return Err(format!("base {} float literals are not supported", radix));
+                }
+                Some(NumberKind::Float) => Some(NumberKind::Float),
+                Some(_) if is_float => {
+                    return Err(format!("invalid suffix `{}` for float literal", suffix));
+                }
+                Some(kind) => Some(kind),
+                None => return Err(format!("invalid suffix `{}` for number literal", suffix)),
+            }
+        };
+        let suffix = if suffix.is_empty() { None } else { Some(suffix.to_string()) };
+
+        if is_float || kind == Some(NumberKind::Float) {
+            let value: f64 = digits.parse().map_err(|_| format!("invalid float literal `{}`", content))?;
+            let value = if negative { -value } else { value };
+            return Ok(Token::literal(TokenType::Double, content, LiteralValue::Float { value, suffix }));
+        }
+
+        let value = u128::from_str_radix(&digits, radix).map_err(|_| "integer literal is too large".to_string())?;
+        let negative = negative && value != 0;
+        let in_range = match kind {
+            Some(NumberKind::Unsigned(bits)) => !negative && (bits >= 128 || value < 1u128 << bits),
+            Some(NumberKind::Signed(bits)) if bits > 128 => true,
+            Some(NumberKind::Signed(bits)) if negative => value <= 1u128 << (bits - 1),
+            Some(NumberKind::Signed(bits)) => value < 1u128 << (bits - 1),
+            _ => true,
+        };
+        if !in_range {
+            return Err(format!("literal out of range for `{}`", suffix.unwrap_or_default()));
+        }
+
+        Ok(Token::literal(TokenType::Integer, content, LiteralValue::Integer { value, negative, suffix }))
+    }
+
+    fn eat_decimal_digits(&mut self) {
+        while self.bump_if(|c| c.is_ascii_digit() || c == '_').is_some() {}
+    }
+
+    fn has_exponent(&self) -> bool {
+        if !matches!(self.current(), Some('e') | Some('E')) {
+            return false;
+        }
+        let mut chars = self.input[self.pos..].chars().skip(1);
+        match chars.next() {
+            Some('+') | Some('-') => {
+                self.touch(3);
+                chars.next().is_some_and(|c| c.is_ascii_digit())
+            }
+            next => {
+                self.touch(2);
+                next.is_some_and(|c| c.is_ascii_digit())
+            }
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LexOutput {
+    pub tokens: Vec<Token>,
+    pub diagnostics: Vec<LexError>,
+    pub(crate) lookahead: Vec<usize>,
+}
+
+impl LexOutput {
+    pub fn identifiers(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.tokens
+            .iter()
+            .filter(|t| t.type_name == Identifier && seen.insert(t.content.as_str()))
+            .map(|t| t.content.as_str())
+            .collect()
+    }
+
+    pub(crate) fn push(&mut self, lexer: &mut Lexer, result: Result<Token, LexError>) {
+        self.diagnostics.extend(lexer.take_warnings());
+        let token = match result {
+            Ok(token) => token,
+            Err(error) => {
+                let span = error.span();
+                self.diagnostics.push(error);
+                let mut token = Token::new(Invalid, lexer.input[span.start..span.end].to_string());
+                token.span = span;
+                token
+            }
+        };
+        self.lookahead.push(lexer.reach().saturating_sub(token.span.end));
+        self.tokens.push(token);
+    }
+}
+
+pub fn lex(input: &str) -> LexOutput {
+    lex_with_options(input, LexerOptions::default())
+}
+
+pub fn lex_with_options(input: &str, options: LexerOptions) -> LexOutput {
+    lex_with_lexicon(input, &DEFAULT_LEXICON, options)
+}
+
+pub fn lex_with_lexicon(input: &str, lexicon: &Lexicon, options: LexerOptions) -> LexOutput {
+    let mut output = LexOutput::default();
+
+    let mut lexer = Lexer::with_lexicon(input, lexicon, options);
+    while let Some(result) = lexer.next() {
+        output.push(&mut lexer, result);
+    }
+
+    output
+}
+
+fn escape_special_chars(input: &str) -> String {
+    let mut escaped = String::new();
+    for c in input.chars() {
+        if r"\^$.*+?()[]{}|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub(crate) fn generate_regex<S: AsRef<str>>(expr: &[S]) -> Regex {
+    let escaped_expr: Vec<String> = expr.iter().map(|s| escape_special_chars(s.as_ref())).collect();
+    let content = format!("^({})$", escaped_expr.join("|"));
+    Regex::new(&content).unwrap()
+}
+
+fn is_match_char(regex: &Regex, c: char) -> bool {
+    regex.is_match(c.encode_utf8(&mut [0; 4]))
+}
@@ -0,0 +1,36 @@
+pub mod token;
+pub mod config;
+pub mod confusables;
+pub mod error;
+pub mod lexer;
+pub mod incremental;
+pub mod stream;
+pub mod spec;
+pub mod dfa;
+pub mod operators;
+pub mod diagnostic;
+pub mod highlight;
+pub mod format;
+pub mod lsp;
+pub mod ast;
+pub mod parser;
+pub mod symbols;
+pub mod stats;
+pub mod interpreter;
+pub mod cli;
+
+pub use crate::config::{ConfigError, LexerConfig, Lexicon};
+pub use crate::dfa::DfaLexer;
+pub use crate::diagnostic::{render_diagnostic, render_diagnostic_location, Diagnostic};
+pub use crate::format::{format_source, format_source_with_lexicon};
+pub use crate::highlight::{render_ansi, render_html};
+pub use crate::error::{EditError, LexError, ParseError, RuntimeError, SymbolError};
+pub use crate::interpreter::{interpret, interpret_function, FloatType, IntType, Value, ValueType};
+pub use crate::incremental::{relex, relex_with_lexicon, relex_with_options, TextEdit};
+pub use crate::lexer::{lex, lex_with_lexicon, lex_with_options, IdentifierMode, LexOutput, Lexer, LexerOptions};
+pub use crate::parser::{parse, ParseOutput, Parser};
+pub use crate::spec::{SpecError, TokenRule, TokenSpec};
+pub use crate::stats::{source_stats, SourceStats};
+pub use crate::stream::StreamLexer;
+pub use crate::symbols::{resolve, ResolveOutput, Symbol, SymbolKind, SymbolTable};
+pub use crate::token::{LiteralValue, Position, Span, Token, TokenType};
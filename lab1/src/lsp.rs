@@ -0,0 +1,167 @@
+use lsp_types::{
+    Diagnostic as LspDiagnostic, DiagnosticSeverity, DocumentSymbol, Position as LspPosition, Range, SemanticToken,
+    SemanticTokenType, SemanticTokensLegend, SymbolKind as LspSymbolKind,
+};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::LexOutput;
+use crate::parser::ParseOutput;
+use crate::symbols::{ResolveOutput, SymbolKind};
+use crate::token::{Span, TokenType};
+
+pub const SEMANTIC_TOKEN_TYPES: [SemanticTokenType; 11] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::STRING,
+    SemanticTokenType::COMMENT,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::DECORATOR,
+    SemanticTokenType::MACRO,
+];
+
+pub fn semantic_token_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend { token_types: SEMANTIC_TOKEN_TYPES.to_vec(), token_modifiers: Vec::new() }
+}
+
+pub fn semantic_token_type(token_type: TokenType) -> Option<SemanticTokenType> {
+    let semantic = match token_type {
+        TokenType::KeyWord | TokenType::Boolean => SemanticTokenType::KEYWORD,
+        TokenType::DataType => SemanticTokenType::TYPE,
+        TokenType::Integer | TokenType::Double => SemanticTokenType::NUMBER,
+        TokenType::Identifier => SemanticTokenType::VARIABLE,
+        TokenType::Arithmetic | TokenType::Comparison | TokenType::Logical | TokenType::Assignment => SemanticTokenType::OPERATOR,
+        TokenType::Function => SemanticTokenType::FUNCTION,
+        TokenType::StringLiteral | TokenType::CharLiteral => SemanticTokenType::STRING,
+        TokenType::Comment => SemanticTokenType::COMMENT,
+        TokenType::Lifetime => SemanticTokenType::TYPE_PARAMETER,
+        TokenType::Attribute => SemanticTokenType::DECORATOR,
+        TokenType::Macro => SemanticTokenType::MACRO,
+        TokenType::Delimiter | TokenType::Whitespace | TokenType::Newline | TokenType::Invalid => return None,
+    };
+    Some(semantic)
+}
+
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        LineIndex { source, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> LspPosition {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = self.source[self.line_starts[line]..offset].encode_utf16().count();
+        LspPosition { line: line as u32, character: character as u32 }
+    }
+
+    pub fn range(&self, span: Span) -> Range {
+        Range { start: self.position(span.start), end: self.position(span.end) }
+    }
+
+    fn line_end(&self, line: usize) -> usize {
+        self.line_starts.get(line + 1).map_or(self.source.len(), |&next| next - 1)
+    }
+}
+
+pub fn semantic_tokens(source: &str, output: &LexOutput) -> Vec<SemanticToken> {
+    let index = LineIndex::new(source);
+    let mut encoded = Vec::new();
+    let (mut previous_line, mut previous_start) = (0, 0);
+
+    for token in &output.tokens {
+        let Some(semantic) = semantic_token_type(token.type_name) else { continue };
+        let token_type = SEMANTIC_TOKEN_TYPES.iter().position(|t| *t == semantic).unwrap() as u32;
+
+        let start = index.position(token.span.start);
+        let end = index.position(token.span.end);
+        for line in start.line..=end.line {
+            let from = if line == start.line { start.character } else { 0 };
+            let to = if line == end.line {
+                end.character
+            } else {
+                index.position(index.line_end(line as usize)).character
+            };
+            if to <= from {
+                continue;
+            }
+            let delta_line = line - previous_line;
+            let delta_start = if delta_line == 0 { from - previous_start } else { from };
+            encoded.push(SemanticToken { delta_line, delta_start, length: to - from, token_type, token_modifiers_bitset: 0 });
+            previous_line = line;
+            previous_start = from;
+        }
+    }
+    encoded
+}
+
+fn to_lsp_diagnostic(index: &LineIndex, error: &dyn Diagnostic, source: &str) -> LspDiagnostic {
+    let severity = if error.level() == "warning" { DiagnosticSeverity::WARNING } else { DiagnosticSeverity::ERROR };
+    LspDiagnostic {
+        range: index.range(error.span()),
+        severity: Some(severity),
+        source: Some(source.to_string()),
+        message: error.to_string(),
+        ..LspDiagnostic::default()
+    }
+}
+
+pub fn diagnostics(source: &str, lexed: &LexOutput, parsed: &ParseOutput, resolved: &ResolveOutput) -> Vec<LspDiagnostic> {
+    let index = LineIndex::new(source);
+    let mut diagnostics: Vec<LspDiagnostic> = lexed.diagnostics.iter().map(|e| to_lsp_diagnostic(&index, e, "lab1-lexer")).collect();
+
+    for token in lexed.tokens.iter().filter(|t| t.type_name == TokenType::Invalid) {
+        if !lexed.diagnostics.iter().any(|e| e.span().start == token.span.start) {
+            diagnostics.push(LspDiagnostic {
+                range: index.range(token.span),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("lab1-lexer".to_string()),
+                message: format!("unexpected character `{}`", token.content),
+                ..LspDiagnostic::default()
+            });
+        }
+    }
+
+    diagnostics.extend(parsed.diagnostics.iter().map(|e| to_lsp_diagnostic(&index, e, "lab1-parser")));
+    diagnostics.extend(resolved.diagnostics.iter().map(|e| to_lsp_diagnostic(&index, e, "lab1-symbols")));
+    diagnostics
+}
+
+fn symbol_kind(kind: SymbolKind) -> LspSymbolKind {
+    match kind {
+        SymbolKind::Variable | SymbolKind::Parameter => LspSymbolKind::VARIABLE,
+        SymbolKind::Function | SymbolKind::Macro => LspSymbolKind::FUNCTION,
+        SymbolKind::Struct => LspSymbolKind::STRUCT,
+        SymbolKind::Field => LspSymbolKind::FIELD,
+        SymbolKind::Enum => LspSymbolKind::ENUM,
+        SymbolKind::Variant => LspSymbolKind::ENUM_MEMBER,
+        SymbolKind::Import => LspSymbolKind::MODULE,
+    }
+}
+
+#[allow(deprecated)]
+pub fn document_symbols(source: &str, resolved: &ResolveOutput) -> Vec<DocumentSymbol> {
+    let index = LineIndex::new(source);
+    resolved
+        .table
+        .symbols
+        .iter()
+        .map(|symbol| DocumentSymbol {
+            name: symbol.name.clone(),
+            detail: symbol.ty.clone(),
+            kind: symbol_kind(symbol.kind),
+            tags: None,
+            deprecated: None,
+            range: index.range(symbol.span),
+            selection_range: index.range(symbol.span),
+            children: None,
+        })
+        .collect()
+}
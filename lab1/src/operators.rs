@@ -0,0 +1,84 @@
+use lazy_static::lazy_static;
+use crate::lexer::{ARITHMETIC_SYMBOLS, ASSIGNMENT_SYMBOLS, ATTRIBUTE_SYMBOLS, COMPARISON_SYMBOLS, DELIMITER_SYMBOLS, LOGICAL_SYMBOLS};
+use crate::token::TokenType;
+
+lazy_static! {
+    pub static ref OPERATORS: OperatorTrie = {
+        let mut trie = OperatorTrie::new();
+        trie.insert_all(&ARITHMETIC_SYMBOLS, TokenType::Arithmetic);
+        trie.insert_all(&COMPARISON_SYMBOLS, TokenType::Comparison);
+        trie.insert_all(&LOGICAL_SYMBOLS, TokenType::Logical);
+        trie.insert_all(&ASSIGNMENT_SYMBOLS, TokenType::Assignment);
+        trie.insert_all(&DELIMITER_SYMBOLS, TokenType::Delimiter);
+        trie.insert_all(&ATTRIBUTE_SYMBOLS, TokenType::Attribute);
+        trie
+    };
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(char, usize)>,
+    token_type: Option<TokenType>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperatorTrie {
+    nodes: Vec<Node>,
+}
+
+impl OperatorTrie {
+    pub fn new() -> Self {
+        OperatorTrie { nodes: vec![Node::default()] }
+    }
+
+    pub fn insert(&mut self, operator: &str, token_type: TokenType) {
+        let mut node = 0;
+        for c in operator.chars() {
+            node = match self.child(node, c) {
+                Some(next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node].children.push((c, next));
+                    next
+                }
+            };
+        }
+        self.nodes[node].token_type = Some(token_type);
+    }
+
+    pub fn insert_all(&mut self, operators: &[&str], token_type: TokenType) {
+        for operator in operators {
+            self.insert(operator, token_type);
+        }
+    }
+
+    pub fn longest_match(&self, input: &str) -> Option<(usize, TokenType)> {
+        self.longest_match_with_reach(input).0
+    }
+
+    pub fn longest_match_with_reach(&self, input: &str) -> (Option<(usize, TokenType)>, usize) {
+        let mut node = 0;
+        let mut longest = None;
+        for (offset, c) in input.char_indices() {
+            match self.child(node, c) {
+                Some(next) => node = next,
+                None => return (longest, offset + c.len_utf8()),
+            }
+            if let Some(token_type) = self.nodes[node].token_type {
+                longest = Some((offset + c.len_utf8(), token_type));
+            }
+        }
+        (longest, input.len() + 1)
+    }
+
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node].children.iter().find(|(key, _)| *key == c).map(|(_, next)| *next)
+    }
+}
+
+impl Default for OperatorTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,965 @@
+use std::mem;
+use std::panic;
+use std::thread;
+use crate::ast::*;
+use crate::error::ParseError;
+use crate::token::{LiteralValue, Span, Token, TokenType};
+
+const ITEM_KEYWORDS: [&str; 7] = ["fn", "struct", "enum", "impl", "use", "macro", "#"];
+const MAX_NESTING: usize = 128;
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+const STATEMENT_KEYWORDS: [&str; 12] = ["let", "fn", "struct", "enum", "impl", "use", "macro", "#", "if", "while", "loop", "for"];
+
+#[derive(Debug, Clone)]
+pub struct ParseOutput {
+    pub program: Program,
+    pub diagnostics: Vec<ParseError>,
+}
+
+pub fn parse(tokens: &[Token]) -> ParseOutput {
+    thread::scope(|scope| {
+        let worker = thread::Builder::new()
+            .name("lab1-parser".to_string())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || {
+                let mut parser = Parser::new(tokens);
+                let program = parser.parse_program();
+                ParseOutput { program, diagnostics: parser.errors }
+            })
+            .expect("failed to spawn the parser thread");
+        worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+pub struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    last: Span,
+    eof: Span,
+    no_struct_literal: bool,
+    split_shift: bool,
+    depth: usize,
+    errors: Vec<ParseError>,
+}
+
+impl Parser {
+    pub fn new(tokens: &[Token]) -> Self {
+        let tokens = split_signed_literals(tokens);
+        let eof = tokens.last().map_or(Span { start: 0, end: 0, line: 1, column: 1 }, |token| {
+            let (line, column) = match token.content.rfind('\n') {
+                Some(i) => (token.span.line + token.content.matches('\n').count(), token.content[i + 1..].chars().count() + 1),
+                None => (token.span.line, token.span.column + token.content.chars().count()),
+            };
+            Span { start: token.span.end, end: token.span.end, line, column }
+        });
+        Parser { tokens, pos: 0, last: eof, eof, no_struct_literal: false, split_shift: false, depth: 0, errors: Vec::new() }
+    }
+
+    pub fn diagnostics(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + n)
+    }
+
+    fn current_span(&self) -> Span {
+        self.peek().map_or(self.eof, |t| t.span)
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        self.last = token.span;
+        self.pos += 1;
+        token
+    }
+
+    fn at(&self, symbol: &str) -> bool {
+        self.peek().is_some_and(|t| is_symbol(t, symbol))
+    }
+
+    fn at_any(&self, symbols: &[&str]) -> bool {
+        symbols.iter().any(|s| self.at(s))
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if self.at(symbol) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<Span, ParseError> {
+        if self.at(symbol) {
+            Ok(self.bump().span)
+        } else {
+            Err(self.error_here(&format!("`{}`", symbol)))
+        }
+    }
+
+    fn error_here(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => ParseError::UnexpectedToken {
+                span: token.span,
+                expected: expected.to_string(),
+                found: token.content.clone(),
+            },
+            None => ParseError::UnexpectedEof { span: self.eof, expected: expected.to_string() },
+        }
+    }
+
+    fn expect_ident(&mut self, expected: &str) -> Result<Ident, ParseError> {
+        match self.peek() {
+            Some(token) if is_word(token) => Ok(to_ident(self.bump())),
+            _ => Err(self.error_here(expected)),
+        }
+    }
+
+    fn expect_name(&mut self, expected: &str) -> Result<Ident, ParseError> {
+        match self.peek() {
+            Some(token) if is_word(token) || token.type_name == TokenType::Function => Ok(to_ident(self.bump())),
+            _ => Err(self.error_here(expected)),
+        }
+    }
+
+    fn synchronize(&mut self, start: usize) {
+        let mut depth = 0usize;
+        while self.peek().is_some() {
+            if depth == 0 && self.at_any(&STATEMENT_KEYWORDS) && self.pos > start {
+                return;
+            }
+            if self.at("{") {
+                depth += 1;
+            } else if self.at("}") {
+                if depth == 0 {
+                    return;
+                }
+                depth -= 1;
+                if depth == 0 {
+                    self.bump();
+                    return;
+                }
+            } else if depth == 0 && self.at(";") {
+                self.bump();
+                return;
+            }
+            self.bump();
+        }
+    }
+
+    fn comma_list<T>(&mut self, close: &str, mut element: impl FnMut(&mut Self) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+        let mut elements = Vec::new();
+        while !self.at(close) {
+            elements.push(element(self)?);
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.expect(close)?;
+        Ok(elements)
+    }
+
+    fn without_restriction<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let saved = mem::replace(&mut self.no_struct_literal, false);
+        let result = f(self);
+        self.no_struct_literal = saved;
+        result
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        if self.depth >= MAX_NESTING {
+            return Err(ParseError::NestingTooDeep { span: self.current_span() });
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let mut attributes = Vec::new();
+        let mut items = Vec::new();
+        let mut recovering = false;
+        while self.peek().is_some() {
+            if self.at("#!") || self.at_any(&ITEM_KEYWORDS) {
+                recovering = false;
+                let start = self.pos;
+                let result = if self.at("#!") {
+                    self.parse_attribute().map(|attribute| attributes.push(attribute))
+                } else {
+                    self.parse_item().map(|item| items.push(item))
+                };
+                match result {
+                    Ok(()) => {}
+                    Err(error) => {
+                        self.errors.push(error);
+                        self.synchronize(start);
+                        if self.pos == start {
+                            self.bump();
+                        }
+                        self.eat("}");
+                    }
+                }
+            } else {
+                if !recovering {
+                    self.errors.push(self.error_here("item"));
+                    recovering = true;
+                }
+                self.bump();
+            }
+        }
+        let span = match (items.first(), items.last()) {
+            (Some(first), Some(last)) => first.span.to(last.span),
+            _ => Span { start: 0, end: self.eof.end, line: 1, column: 1 },
+        };
+        Program { attributes, items, span }
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute, ParseError> {
+        let start = self.current_span();
+        let inner = self.bump().content == "#!";
+        self.expect("[")?;
+        let path = self.parse_path()?;
+        let mut args = Vec::new();
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            if depth == 0 && is_symbol(token, "]") {
+                break;
+            }
+            if ["(", "[", "{"].iter().any(|open| is_symbol(token, open)) {
+                depth += 1;
+            } else if [")", "]", "}"].iter().any(|close| is_symbol(token, close)) {
+                depth = depth.saturating_sub(1);
+            }
+            args.push(self.bump());
+        }
+        self.expect("]")?;
+        Ok(Attribute { inner, path, args, span: start.to(self.last) })
+    }
+
+    fn parse_token_tree(&mut self, open: &str, close: &str) -> Result<Vec<Token>, ParseError> {
+        self.expect(open)?;
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            if is_symbol(token, open) {
+                depth += 1;
+            } else if is_symbol(token, close) {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            tokens.push(self.bump());
+        }
+        self.expect(close)?;
+        Ok(tokens)
+    }
+
+    fn parse_generic_params(&mut self) -> Result<Vec<Ident>, ParseError> {
+        let mut generics = Vec::new();
+        if !self.eat("<") {
+            return Ok(generics);
+        }
+        while !self.at_closing_angle() {
+            match self.peek() {
+                Some(token) if token.type_name == TokenType::Lifetime || is_word(token) => generics.push(to_ident(self.bump())),
+                _ => return Err(self.error_here("generic parameter")),
+            }
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.expect_closing_angle()?;
+        Ok(generics)
+    }
+
+    fn parse_item(&mut self) -> Result<Item, ParseError> {
+        let start = self.current_span();
+        let mut attributes = Vec::new();
+        while self.at("#") {
+            attributes.push(self.parse_attribute()?);
+        }
+        let kind = if self.eat("fn") {
+            ItemKind::Function(self.parse_function()?)
+        } else if self.eat("struct") {
+            ItemKind::Struct(self.parse_struct()?)
+        } else if self.eat("enum") {
+            ItemKind::Enum(self.parse_enum()?)
+        } else if self.eat("impl") {
+            ItemKind::Impl(self.parse_impl()?)
+        } else if self.eat("use") {
+            ItemKind::Use(self.parse_use()?)
+        } else if self.eat("macro") {
+            let name = self.expect_ident("macro name")?;
+            let params = if self.at("(") { self.parse_token_tree("(", ")")? } else { Vec::new() };
+            let body = self.parse_token_tree("{", "}")?;
+            ItemKind::Macro(MacroDef { name, params, body })
+        } else {
+            return Err(self.error_here("item"));
+        };
+        Ok(Item { attributes, kind, span: start.to(self.last) })
+    }
+
+    fn parse_function(&mut self) -> Result<Function, ParseError> {
+        let name = self.expect_name("function name")?;
+        let generics = self.parse_generic_params()?;
+        self.expect("(")?;
+        let params = self.comma_list(")", Self::parse_param)?;
+        let return_type = if self.eat("->") { Some(self.parse_type()?) } else { None };
+        let body = self.parse_block()?;
+        Ok(Function { name, generics, params, return_type, body })
+    }
+
+    fn parse_param(&mut self) -> Result<Param, ParseError> {
+        let start = self.current_span();
+        if self.at("&") && self.peek_nth(1).is_some_and(|t| t.content == "self" || is_symbol(t, "mut")) {
+            self.bump();
+            let mutable = self.eat("mut");
+            let name = self.expect_ident("`self`")?;
+            let self_type = Type { kind: self_type_path(name.span), span: name.span };
+            let ty = Type { kind: TypeKind::Reference { lifetime: None, mutable, inner: Box::new(self_type) }, span: start.to(name.span) };
+            return Ok(Param { mutable: false, name, ty: Some(ty), span: start.to(self.last) });
+        }
+        let mutable = self.eat("mut");
+        let name = self.expect_ident("parameter name")?;
+        let ty = if name.name == "self" && !self.at(":") {
+            None
+        } else {
+            self.expect(":")?;
+            Some(self.parse_type()?)
+        };
+        Ok(Param { mutable, name, ty, span: start.to(self.last) })
+    }
+
+    fn parse_struct(&mut self) -> Result<Struct, ParseError> {
+        let name = self.expect_ident("struct name")?;
+        let generics = self.parse_generic_params()?;
+        let fields = if self.at("{") {
+            self.parse_fields()?
+        } else {
+            let fields = if self.at("(") { self.parse_fields()? } else { Fields::Unit };
+            self.expect(";")?;
+            fields
+        };
+        Ok(Struct { name, generics, fields })
+    }
+
+    fn parse_fields(&mut self) -> Result<Fields, ParseError> {
+        if self.eat("(") {
+            return Ok(Fields::Tuple(self.comma_list(")", Self::parse_type)?));
+        }
+        if !self.eat("{") {
+            return Ok(Fields::Unit);
+        }
+        let fields = self.comma_list("}", |parser| {
+            let name = parser.expect_ident("field name")?;
+            parser.expect(":")?;
+            let ty = parser.parse_type()?;
+            Ok(Field { span: name.span.to(ty.span), name, ty })
+        })?;
+        Ok(Fields::Named(fields))
+    }
+
+    fn parse_enum(&mut self) -> Result<Enum, ParseError> {
+        let name = self.expect_ident("enum name")?;
+        let generics = self.parse_generic_params()?;
+        self.expect("{")?;
+        let variants = self.comma_list("}", |parser| {
+            let name = parser.expect_ident("variant name")?;
+            let fields = parser.parse_fields()?;
+            Ok(Variant { span: name.span.to(parser.last), name, fields })
+        })?;
+        Ok(Enum { name, generics, variants })
+    }
+
+    fn parse_impl(&mut self) -> Result<Impl, ParseError> {
+        let first = self.parse_type()?;
+        let (trait_name, self_type) = if self.eat("for") {
+            match first.kind {
+                TypeKind::Path { path, .. } => (Some(path), self.parse_type()?),
+                _ => return Err(ParseError::UnexpectedToken {
+                    span: first.span,
+                    expected: "trait name".to_string(),
+                    found: "type".to_string(),
+                }),
+            }
+        } else {
+            (None, first)
+        };
+
+        self.expect("{")?;
+        let mut functions = Vec::new();
+        while !self.at("}") && self.peek().is_some() {
+            let start = self.pos;
+            let result = if self.at("fn") { self.parse_item() } else { Err(self.error_here("`fn`")) };
+            match result {
+                Ok(item) => functions.push(item),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize(start);
+                    if self.pos == start {
+                        self.bump();
+                    }
+                }
+            }
+        }
+        self.expect("}")?;
+        Ok(Impl { trait_name, self_type, functions })
+    }
+
+    fn parse_use(&mut self) -> Result<Use, ParseError> {
+        let first = self.expect_name("path")?;
+        let mut path = Path { span: first.span, segments: vec![first] };
+        let mut glob = false;
+        while self.eat("::") {
+            if self.eat("*") {
+                glob = true;
+                break;
+            }
+            let segment = self.parse_path_segment()?;
+            path.span = path.span.to(segment.span);
+            path.segments.push(segment);
+        }
+        self.expect(";")?;
+        Ok(Use { path, glob })
+    }
+
+    fn parse_path_segment(&mut self) -> Result<Ident, ParseError> {
+        match self.peek() {
+            Some(token) if is_word(token) || matches!(token.type_name, TokenType::DataType | TokenType::Function) => {
+                Ok(to_ident(self.bump()))
+            }
+            _ => Err(self.error_here("path segment")),
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Path, ParseError> {
+        let first = self.parse_path_segment()?;
+        let mut path = Path { span: first.span, segments: vec![first] };
+        while self.at("::") && self.peek_nth(1).is_some_and(|t| is_word(t) || matches!(t.type_name, TokenType::DataType | TokenType::Function)) {
+            self.bump();
+            let segment = self.parse_path_segment()?;
+            path.span = path.span.to(segment.span);
+            path.segments.push(segment);
+        }
+        Ok(path)
+    }
+
+    pub fn parse_type(&mut self) -> Result<Type, ParseError> {
+        self.nested(Self::parse_type_inner)
+    }
+
+    fn parse_type_inner(&mut self) -> Result<Type, ParseError> {
+        let start = self.current_span();
+        let kind = if self.eat("&") {
+            let lifetime = match self.peek() {
+                Some(token) if token.type_name == TokenType::Lifetime => Some(to_ident(self.bump())),
+                _ => None,
+            };
+            let mutable = self.eat("mut");
+            TypeKind::Reference { lifetime, mutable, inner: Box::new(self.parse_type()?) }
+        } else if self.eat("[") {
+            let element = Box::new(self.parse_type()?);
+            let length = if self.eat(";") { Some(Box::new(self.without_restriction(Self::parse_expression)?)) } else { None };
+            self.expect("]")?;
+            TypeKind::Array { element, length }
+        } else if self.eat("(") {
+            TypeKind::Tuple(self.comma_list(")", Self::parse_type)?)
+        } else if self.peek().is_some_and(|t| is_word(t) || t.type_name == TokenType::DataType) {
+            let path = self.parse_path()?;
+            let mut generics = Vec::new();
+            if self.eat("<") {
+                while !self.at_closing_angle() {
+                    if self.peek().is_some_and(|t| t.type_name == TokenType::Lifetime) {
+                        self.bump();
+                    } else {
+                        generics.push(self.parse_type()?);
+                    }
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+                self.expect_closing_angle()?;
+            }
+            TypeKind::Path { path, generics }
+        } else {
+            return Err(self.error_here("type"));
+        };
+        Ok(Type { kind, span: start.to(self.last) })
+    }
+
+    fn at_closing_angle(&self) -> bool {
+        self.split_shift || self.at(">") || self.at(">>")
+    }
+
+    fn expect_closing_angle(&mut self) -> Result<(), ParseError> {
+        if self.split_shift {
+            self.split_shift = false;
+            self.bump();
+        } else if self.at(">>") {
+            self.split_shift = true;
+            self.last = self.current_span();
+        } else {
+            self.expect(">")?;
+        }
+        Ok(())
+    }
+
+    pub fn parse_block(&mut self) -> Result<Block, ParseError> {
+        self.nested(Self::parse_block_inner)
+    }
+
+    fn parse_block_inner(&mut self) -> Result<Block, ParseError> {
+        let start = self.expect("{")?;
+        let saved = mem::replace(&mut self.no_struct_literal, false);
+        let mut statements = Vec::new();
+        let mut tail = None;
+
+        while !self.at("}") && self.peek().is_some() {
+            let start = self.pos;
+            match self.parse_statement() {
+                Ok(Some(statement)) => statements.push(statement),
+                Ok(None) => {}
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize(start);
+                    if self.pos == start {
+                        self.bump();
+                    }
+                    continue;
+                }
+            }
+            if let Some(Stmt { kind: StmtKind::Expr(_), .. }) = statements.last() {
+                if self.at("}") {
+                    if let Some(Stmt { kind: StmtKind::Expr(expr), .. }) = statements.pop() {
+                        tail = Some(Box::new(expr));
+                    }
+                }
+            }
+        }
+
+        self.no_struct_literal = saved;
+        self.expect("}")?;
+        Ok(Block { statements, tail, span: start.to(self.last) })
+    }
+
+    fn parse_statement(&mut self) -> Result<Option<Stmt>, ParseError> {
+        let start = self.current_span();
+        if self.eat(";") {
+            return Ok(None);
+        }
+
+        let kind = if self.eat("let") {
+            let mutable = self.eat("mut");
+            let name = if self.at("_") { to_ident(self.bump()) } else { self.expect_ident("variable name")? };
+            let ty = if self.eat(":") { Some(self.parse_type()?) } else { None };
+            let init = if self.eat("=") { Some(self.parse_expression_inner()?) } else { None };
+            self.expect(";")?;
+            StmtKind::Let { mutable, name, ty, init }
+        } else if self.at_any(&ITEM_KEYWORDS) {
+            StmtKind::Item(self.parse_item()?)
+        } else {
+            let labeled = self.peek().is_some_and(|t| t.type_name == TokenType::Lifetime);
+            let expr = if labeled || self.at_any(&["if", "while", "loop", "for", "{"]) {
+                self.parse_primary()?
+            } else {
+                self.parse_expression_inner()?
+            };
+            if self.eat(";") {
+                StmtKind::Semi(expr)
+            } else if self.at("}") || expr.kind.is_block_like() {
+                StmtKind::Expr(expr)
+            } else {
+                return Err(self.error_here("`;` or `}`"));
+            }
+        };
+        Ok(Some(Stmt { kind, span: start.to(self.last) }))
+    }
+
+    pub fn parse_expression(&mut self) -> Result<Expr, ParseError> {
+        self.nested(Self::parse_expression_inner)
+    }
+
+    fn parse_expression_inner(&mut self) -> Result<Expr, ParseError> {
+        let target = self.parse_range()?;
+        let op = match self.peek() {
+            Some(token) if token.type_name == TokenType::Assignment => token.content.clone(),
+            _ => return Ok(target),
+        };
+        self.bump();
+        let value = self.parse_expression()?;
+        if !target.kind.is_place() {
+            self.errors.push(ParseError::InvalidAssignmentTarget { span: target.span });
+        }
+        let span = target.span.to(value.span);
+        let op = BinaryOp::from_symbol(&op[..op.len() - 1]);
+        Ok(Expr { kind: ExprKind::Assign { op, target: Box::new(target), value: Box::new(value) }, span })
+    }
+
+    fn parse_condition(&mut self) -> Result<Expr, ParseError> {
+        let saved = mem::replace(&mut self.no_struct_literal, true);
+        let condition = self.parse_expression();
+        self.no_struct_literal = saved;
+        condition
+    }
+
+    fn parse_range(&mut self) -> Result<Expr, ParseError> {
+        let start = self.current_span();
+        let lhs = if self.at("..") || self.at("..=") { None } else { Some(self.parse_binary(1)?) };
+        if !(self.at("..") || self.at("..=")) {
+            return Ok(lhs.unwrap());
+        }
+        let inclusive = self.bump().content == "..=";
+        let end = if self.can_start_expression() {
+            Some(Box::new(self.parse_binary(1)?))
+        } else if inclusive {
+            return Err(self.error_here("range end"));
+        } else {
+            None
+        };
+        let span = start.to(self.last);
+        Ok(Expr { kind: ExprKind::Range { start: lhs.map(Box::new), end, inclusive }, span })
+    }
+
+    fn can_start_expression(&self) -> bool {
+        let Some(token) = self.peek() else { return false };
+        match token.type_name {
+            TokenType::Integer | TokenType::Double | TokenType::StringLiteral | TokenType::CharLiteral
+            | TokenType::Boolean | TokenType::Identifier | TokenType::Function | TokenType::DataType
+            | TokenType::Invalid | TokenType::Macro | TokenType::Lifetime => true,
+            TokenType::KeyWord => self.at_any(&["if", "while", "loop", "for", "break", "continue", "return"]),
+            _ => self.at_any(&["(", "[", "-", "!", "*", "&", "&&"]) || (self.at("{") && !self.no_struct_literal),
+        }
+    }
+
+    fn peek_binary_op(&self) -> Option<BinaryOp> {
+        let token = self.peek()?;
+        match token.type_name {
+            TokenType::Arithmetic | TokenType::Comparison | TokenType::Logical => BinaryOp::from_symbol(&token.content),
+            TokenType::Delimiter if token.content == "&" => Some(BinaryOp::BitAnd),
+            _ => None,
+        }
+    }
+
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(op) = self.peek_binary_op().filter(|op| op.precedence() >= min_precedence) {
+            self.bump();
+            let rhs = self.parse_binary(op.precedence() + 1)?;
+            if op.is_comparison() && matches!(lhs.kind, ExprKind::Binary { op, .. } if op.is_comparison()) {
+                self.errors.push(ParseError::ChainedComparison { span: lhs.span.to(rhs.span) });
+            }
+            let span = lhs.span.to(rhs.span);
+            lhs = Expr { kind: ExprKind::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs) }, span };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if self.at_any(&["-", "!", "*", "&", "&&"]) {
+            self.nested(Self::parse_unary_inner)
+        } else {
+            self.parse_postfix()
+        }
+    }
+
+    fn parse_unary_inner(&mut self) -> Result<Expr, ParseError> {
+        let start = self.current_span();
+        let op = if self.eat("-") {
+            UnaryOp::Neg
+        } else if self.eat("!") {
+            UnaryOp::Not
+        } else if self.eat("*") {
+            UnaryOp::Deref
+        } else if self.eat("&") {
+            if self.eat("mut") { UnaryOp::RefMut } else { UnaryOp::Ref }
+        } else if self.eat("&&") {
+            let inner_op = if self.eat("mut") { UnaryOp::RefMut } else { UnaryOp::Ref };
+            let operand = self.parse_unary()?;
+            let inner = Expr { span: start.to(operand.span), kind: ExprKind::Unary { op: inner_op, operand: Box::new(operand) } };
+            return Ok(Expr { span: inner.span, kind: ExprKind::Unary { op: UnaryOp::Ref, operand: Box::new(inner) } });
+        } else {
+            return self.parse_postfix();
+        };
+        let operand = self.parse_unary()?;
+        Ok(Expr { span: start.to(operand.span), kind: ExprKind::Unary { op, operand: Box::new(operand) } })
+    }
+
+    fn parse_call_args(&mut self) -> Result<Vec<Expr>, ParseError> {
+        self.expect("(")?;
+        self.without_restriction(|parser| parser.comma_list(")", Self::parse_expression))
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let start = expr.span;
+            let kind = if self.at("(") {
+                let args = self.parse_call_args()?;
+                ExprKind::Call { callee: Box::new(expr), args }
+            } else if self.eat("[") {
+                let index = self.without_restriction(Self::parse_expression)?;
+                self.expect("]")?;
+                ExprKind::Index { base: Box::new(expr), index: Box::new(index) }
+            } else if self.eat(".") {
+                match self.peek() {
+                    Some(token) if token.type_name == TokenType::Double && is_tuple_index_pair(&token.content) => {
+                        let token = self.bump();
+                        let (first, second) = token.content.split_once('.').unwrap();
+                        let first_span = Span { end: token.span.start + first.len(), ..token.span };
+                        let second_span = Span {
+                            start: first_span.end + 1,
+                            end: token.span.end,
+                            line: token.span.line,
+                            column: token.span.column + first.len() + 1,
+                        };
+                        let inner = Expr {
+                            span: start.to(first_span),
+                            kind: ExprKind::Field { base: Box::new(expr), field: Ident { name: first.to_string(), span: first_span } },
+                        };
+                        ExprKind::Field { base: Box::new(inner), field: Ident { name: second.to_string(), span: second_span } }
+                    }
+                    Some(token) if token.type_name == TokenType::Integer && token.content.bytes().all(|b| b.is_ascii_digit()) => {
+                        ExprKind::Field { base: Box::new(expr), field: to_ident(self.bump()) }
+                    }
+                    _ => {
+                        let name = self.expect_name("field or method name")?;
+                        if self.at("(") {
+                            let args = self.parse_call_args()?;
+                            ExprKind::MethodCall { receiver: Box::new(expr), method: name, args }
+                        } else {
+                            ExprKind::Field { base: Box::new(expr), field: name }
+                        }
+                    }
+                }
+            } else if self.eat("?") {
+                ExprKind::Try(Box::new(expr))
+            } else {
+                return Ok(expr);
+            };
+            expr = Expr { kind, span: start.to(self.last) };
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        let Some(token) = self.peek() else {
+            return Err(self.error_here("expression"));
+        };
+        let (start, type_name, word) = (token.span, token.type_name, is_word(token));
+
+        let kind = match type_name {
+            TokenType::Integer | TokenType::Double | TokenType::StringLiteral | TokenType::CharLiteral => {
+                let token = self.bump();
+                token.value.map_or(ExprKind::Error, ExprKind::Literal)
+            }
+            TokenType::Boolean => ExprKind::Bool(self.bump().content == "true"),
+            TokenType::Function if self.peek_nth(1).is_some_and(|t| is_symbol(t, "(")) => {
+                let function = to_ident(self.bump());
+                let args = self.parse_call_args()?;
+                ExprKind::BuiltinCall { function, args }
+            }
+            TokenType::Identifier | TokenType::DataType | TokenType::Function => self.parse_path_or_struct_literal()?,
+            TokenType::Invalid if word => self.parse_path_or_struct_literal()?,
+            TokenType::Invalid => {
+                self.bump();
+                ExprKind::Error
+            }
+            TokenType::Macro => {
+                let token = self.bump();
+                let name = Ident { name: token.content.trim_end_matches('!').to_string(), span: token.span };
+                ExprKind::MacroCall { name, args: self.parse_macro_args()? }
+            }
+            TokenType::Lifetime if self.peek_nth(1).is_some_and(|t| is_symbol(t, ":")) => {
+                let label = to_ident(self.bump());
+                self.bump();
+                self.parse_loop(Some(label))?
+            }
+            TokenType::KeyWord if self.eat("if") => return self.parse_if(start),
+            TokenType::KeyWord if self.at_any(&["while", "loop", "for"]) => self.parse_loop(None)?,
+            TokenType::KeyWord if self.at_any(&["break", "continue", "return"]) => self.parse_jump()?,
+            _ if self.at("{") => ExprKind::Block(self.parse_block()?),
+            _ if self.eat("(") => self.without_restriction(|parser| -> Result<ExprKind, ParseError> {
+                if parser.eat(")") {
+                    return Ok(ExprKind::Tuple(Vec::new()));
+                }
+                let first = parser.parse_expression()?;
+                if parser.eat(")") {
+                    return Ok(ExprKind::Paren(Box::new(first)));
+                }
+                parser.expect(",")?;
+                let mut elements = vec![first];
+                elements.extend(parser.comma_list(")", Self::parse_expression)?);
+                Ok(ExprKind::Tuple(elements))
+            })?,
+            _ if self.eat("[") => ExprKind::Array(self.without_restriction(|parser| parser.comma_list("]", Self::parse_expression))?),
+            _ => return Err(self.error_here("expression")),
+        };
+        Ok(Expr { kind, span: start.to(self.last) })
+    }
+
+    fn parse_loop(&mut self, label: Option<Ident>) -> Result<ExprKind, ParseError> {
+        if self.eat("while") {
+            let condition = Box::new(self.parse_condition()?);
+            Ok(ExprKind::While { label, condition, body: self.parse_block()? })
+        } else if self.eat("loop") {
+            Ok(ExprKind::Loop { label, body: self.parse_block()? })
+        } else if self.eat("for") {
+            let binding = if self.at("_") { to_ident(self.bump()) } else { self.expect_ident("loop variable")? };
+            if self.peek().is_some_and(|t| t.content == "in") {
+                self.bump();
+            } else {
+                return Err(self.error_here("`in`"));
+            }
+            let iterable = Box::new(self.parse_condition()?);
+            Ok(ExprKind::For { label, binding, iterable, body: self.parse_block()? })
+        } else {
+            Err(self.error_here("loop after label"))
+        }
+    }
+
+    fn parse_jump(&mut self) -> Result<ExprKind, ParseError> {
+        let keyword = self.bump().content;
+        let label = match self.peek() {
+            Some(token) if keyword != "return" && token.type_name == TokenType::Lifetime => Some(to_ident(self.bump())),
+            _ => None,
+        };
+        if keyword == "continue" {
+            return Ok(ExprKind::Continue { label });
+        }
+        let value = if self.can_start_expression() { Some(Box::new(self.parse_expression()?)) } else { None };
+        if keyword == "return" {
+            Ok(ExprKind::Return(value))
+        } else {
+            Ok(ExprKind::Break { label, value })
+        }
+    }
+
+    fn parse_macro_args(&mut self) -> Result<Vec<Expr>, ParseError> {
+        let close = match self.peek() {
+            Some(token) if is_symbol(token, "(") => ")",
+            Some(token) if is_symbol(token, "[") => "]",
+            Some(token) if is_symbol(token, "{") => "}",
+            _ => return Err(self.error_here("`(`, `[` or `{`")),
+        };
+        self.bump();
+        self.without_restriction(|parser| {
+            let mut args = Vec::new();
+            while !parser.at(close) {
+                args.push(parser.parse_expression()?);
+                if !parser.eat(",") && !parser.eat(";") {
+                    break;
+                }
+            }
+            parser.expect(close)?;
+            Ok(args)
+        })
+    }
+
+    fn parse_path_or_struct_literal(&mut self) -> Result<ExprKind, ParseError> {
+        let path = self.parse_path()?;
+        if !self.at("{") || self.no_struct_literal {
+            return Ok(ExprKind::Path(path));
+        }
+        self.bump();
+        let fields = self.without_restriction(|parser| {
+            parser.comma_list("}", |parser| {
+                let name = parser.expect_ident("field name")?;
+                let value = if parser.eat(":") {
+                    parser.parse_expression()?
+                } else {
+                    let path = Path { segments: vec![name.clone()], span: name.span };
+                    Expr { kind: ExprKind::Path(path), span: name.span }
+                };
+                Ok((name, value))
+            })
+        })?;
+        Ok(ExprKind::StructLiteral { path, fields })
+    }
+
+    fn parse_if(&mut self, start: Span) -> Result<Expr, ParseError> {
+        let condition = Box::new(self.parse_condition()?);
+        let then_branch = self.parse_block()?;
+        let else_branch = if self.eat("else") {
+            let else_start = self.current_span();
+            if self.eat("if") {
+                Some(Box::new(self.nested(|parser| parser.parse_if(else_start))?))
+            } else {
+                let block = self.parse_block()?;
+                Some(Box::new(Expr { span: block.span, kind: ExprKind::Block(block) }))
+            }
+        } else {
+            None
+        };
+        Ok(Expr { kind: ExprKind::If { condition, then_branch, else_branch }, span: start.to(self.last) })
+    }
+}
+
+fn is_symbol(token: &Token, symbol: &str) -> bool {
+    token.content == symbol
+        && !matches!(token.type_name, TokenType::StringLiteral | TokenType::CharLiteral | TokenType::Comment | TokenType::Invalid)
+}
+
+fn is_word(token: &Token) -> bool {
+    match token.type_name {
+        TokenType::Identifier => true,
+        TokenType::Invalid => token.content.chars().next().is_some_and(char::is_alphabetic),
+        _ => false,
+    }
+}
+
+fn is_tuple_index_pair(content: &str) -> bool {
+    content
+        .split_once('.')
+        .is_some_and(|(a, b)| !a.is_empty() && !b.is_empty() && a.bytes().chain(b.bytes()).all(|c| c.is_ascii_digit()))
+}
+
+fn to_ident(token: Token) -> Ident {
+    Ident { name: token.content, span: token.span }
+}
+
+fn self_type_path(span: Span) -> TypeKind {
+    let segment = Ident { name: "Self".to_string(), span };
+    TypeKind::Path { path: Path { segments: vec![segment], span }, generics: Vec::new() }
+}
+
+fn ends_operand(token: &Token) -> bool {
+    is_word(token)
+        || matches!(token.type_name, TokenType::Integer | TokenType::Double | TokenType::StringLiteral
+            | TokenType::CharLiteral | TokenType::Boolean)
+        || is_symbol(token, ")")
+        || is_symbol(token, "]")
+}
+
+fn split_signed_literals(tokens: &[Token]) -> Vec<Token> {
+    let mut result: Vec<Token> = Vec::with_capacity(tokens.len());
+    for token in tokens.iter().filter(|t| !t.is_trivia()) {
+        let signed = matches!(token.type_name, TokenType::Integer | TokenType::Double)
+            && (token.content.starts_with('-') || token.content.starts_with('+'));
+        if !signed || !result.last().is_some_and(ends_operand) {
+            result.push(token.clone());
+            continue;
+        }
+
+        let negative = token.content.starts_with('-');
+        let mut sign = Token::new(TokenType::Arithmetic, token.content[..1].to_string());
+        sign.span = Span { end: token.span.start + 1, ..token.span };
+        let mut literal = token.clone();
+        literal.content = token.content[1..].to_string();
+        literal.span = Span { start: token.span.start + 1, column: token.span.column + 1, ..token.span };
+        literal.value = match token.value.clone() {
+            Some(LiteralValue::Integer { value, negative: sign, suffix }) if negative => Some(LiteralValue::Integer { value, negative: !sign && value != 0, suffix }),
+            Some(LiteralValue::Float { value, suffix }) if negative => Some(LiteralValue::Float { value: -value, suffix }),
+            value => value,
+        };
+        result.push(sign);
+        result.push(literal);
+    }
+    result
+}
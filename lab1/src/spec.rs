@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use unicode_xid::UnicodeXID;
+use crate::config::Lexicon;
+use crate::dfa::{escape_literal, parse_class, CharSet};
+use crate::lexer::{
+    numeric_type, IdentifierMode, LexerOptions, NumberKind, ARITHMETIC_SYMBOLS, ASSIGNMENT_SYMBOLS, ATTRIBUTE_SYMBOLS, COMPARISON_SYMBOLS,
+    DELIMITER_SYMBOLS, LETTER_CLASS, LOGICAL_SYMBOLS, WORD_CLASS,
+};
+use crate::token::TokenType;
+
+const BLANK_CLASS: &str = r"[\t\v\f \u{85}\u{A0}\u{1680}\u{2000}-\u{200A}\u{2028}\u{2029}\u{202F}\u{205F}\u{3000}]";
+const ESCAPE: &str = r#"\\([nrt\\0'"]|x[0-7][0-9a-fA-F]|u\{[0-9a-fA-F_]+\})"#;
+const BYTE_ESCAPE: &str = r#"\\([nrt\\0'"]|x[0-9a-fA-F][0-9a-fA-F])"#;
+const COMMENT_BODY: &str = r"[^*/]|/+[^*/]|\*+[^*/]";
+// Regular patterns cannot count, so block comments are unrolled to this depth; deeper nesting tokenizes differently from `lex`.
+const COMMENT_NESTING: usize = 3;
+
+#[derive(Debug)]
+pub enum SpecError {
+    Io(String),
+    Parse(String),
+    UnknownFormat(String),
+    InvalidPattern { rule: String, message: String },
+    TooManyContexts(usize),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecError::Io(message) => write!(f, "cannot read token spec: {}", message),
+            SpecError::Parse(message) => write!(f, "cannot parse token spec: {}", message),
+            SpecError::UnknownFormat(path) => write!(f, "unknown token spec format: {} (expected .toml or .json)", path),
+            SpecError::InvalidPattern { rule, message } => write!(f, "invalid pattern in {}: {}", rule, message),
+            SpecError::TooManyContexts(max) => write!(f, "too many distinct `not_after` classes (at most {})", max),
+        }
+    }
+}
+
+impl Error for SpecError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenRule {
+    pub kind: TokenType,
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub skip: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub except: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl TokenRule {
+    pub fn new(kind: TokenType, pattern: &str) -> Self {
+        TokenRule { kind, pattern: pattern.to_string(), skip: false, except: None, not_after: None, not_before: None }
+    }
+
+    pub fn skip(mut self, skip: bool) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    pub fn except(mut self, pattern: &str) -> Self {
+        self.except = Some(pattern.to_string());
+        self
+    }
+
+    pub fn not_after(mut self, class: &str) -> Self {
+        self.not_after = Some(class.to_string());
+        self
+    }
+
+    pub fn not_before(mut self, class: &str) -> Self {
+        self.not_before = Some(class.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenSpec {
+    pub definitions: BTreeMap<String, String>,
+    pub rules: Vec<TokenRule>,
+}
+
+impl TokenSpec {
+    pub fn from_toml(source: &str) -> Result<TokenSpec, SpecError> {
+        toml::from_str(source).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    pub fn from_json(source: &str) -> Result<TokenSpec, SpecError> {
+        serde_json::from_str(source).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<TokenSpec, SpecError> {
+        let source = fs::read_to_string(path).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&source),
+            Some("json") => Self::from_json(&source),
+            _ => Err(SpecError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap()
+    }
+
+    pub fn from_lexicon(lexicon: &Lexicon, options: LexerOptions) -> Result<TokenSpec, SpecError> {
+        let config = lexicon.config();
+        let mut spec = TokenSpec::default();
+        let (letter, word_char) = match options.identifiers {
+            IdentifierMode::Restricted => (LETTER_CLASS.to_string(), WORD_CLASS.to_string()),
+            IdentifierMode::Unicode => (
+                CharSet::from_predicate(|c| c.is_xid_start()).to_pattern(),
+                CharSet::from_predicate(|c| c.is_xid_continue()).to_pattern(),
+            ),
+        };
+        let integer_types: Vec<&String> = config.data_types.iter().filter(|t| matches!(numeric_type(lexicon, t), Some(NumberKind::Unsigned(_) | NumberKind::Signed(_)))).collect();
+        let float_types: Vec<&String> = config.data_types.iter().filter(|t| numeric_type(lexicon, t) == Some(NumberKind::Float)).collect();
+
+        spec.define("letter", &letter);
+        spec.define("word_char", &word_char);
+        spec.define("word", "({letter}|_{word_char}){word_char}*");
+        spec.define("alnum", &CharSet::from_predicate(|c| c.is_alphanumeric() || c == '_').to_pattern());
+        spec.define("blank", BLANK_CLASS);
+        spec.define("escape", ESCAPE);
+        spec.define("byte_escape", BYTE_ESCAPE);
+        spec.define("comment_body", COMMENT_BODY);
+        spec.define("comment_0", r"/\*({comment_body})*\*+/");
+        spec.define("unterminated_0", r"/\*({comment_body})*(/*\**|/+\*(.|\n)*)");
+        for depth in 1..=COMMENT_NESTING {
+            spec.define(&format!("comment_{}", depth), &format!(r"/\*({{comment_body}}|/*{{comment_{}}})*\*+/", depth - 1));
+            spec.define(&format!("unterminated_{}", depth), &format!(r"/\*({{comment_body}}|/*{{comment_{0}}})*(/*\**|/*{{unterminated_{0}}})", depth - 1));
+        }
+        spec.define("digits", "[0-9][0-9_]*");
+        spec.define("exponent", "[eE][-+]?[0-9][0-9_]*");
+        spec.define("int_suffix", &alternatives(&integer_types));
+        spec.define("float_suffix", &alternatives(&float_types));
+
+        let keep_comments = options.keep_comments || options.trivia;
+        if options.trivia {
+            spec.rule(TokenRule::new(TokenType::Newline, r"\r?\n"));
+            spec.rule(TokenRule::new(TokenType::Whitespace, r"({blank}|\r)*{blank}"));
+            spec.rule(TokenRule::new(TokenType::Whitespace, r"({blank}|\r)*\r").not_before(r"[\n]"));
+        } else {
+            spec.rule(TokenRule::new(TokenType::Whitespace, r"\s+").skip(true));
+        }
+        spec.rule(TokenRule::new(TokenType::Comment, r"//[^\n]*").skip(!keep_comments));
+        spec.rule(TokenRule::new(TokenType::Comment, &format!("{{comment_{}}}", COMMENT_NESTING)).skip(!keep_comments));
+        spec.rule(TokenRule::new(TokenType::Invalid, &format!("{{unterminated_{}}}", COMMENT_NESTING)));
+
+        spec.rule(TokenRule::new(TokenType::StringLiteral, r#""([^"\\]|{escape}|\\\r?\n)*""#));
+        spec.rule(TokenRule::new(TokenType::StringLiteral, r#"b"([^"\\\u{80}-\u{10FFFF}]|{byte_escape}|\\\r?\n)*""#));
+        spec.rule(TokenRule::new(TokenType::StringLiteral, r#"r"[^"]*"|br"[^"\u{80}-\u{10FFFF}]*""#));
+        spec.rule(TokenRule::new(TokenType::StringLiteral, r##"r#"([^"]|"+[^"#])*"+#|br#"([^"\u{80}-\u{10FFFF}]|"+[^"#\u{80}-\u{10FFFF}])*"+#"##));
+        spec.rule(TokenRule::new(TokenType::Invalid, r#"b?"([^"\\]|\\(.|\n))*(\\|")?"#));
+        spec.rule(TokenRule::new(TokenType::Invalid, r##"b?r"[^"]*"?|b?r#"([^"]|"+[^"#])*("+#|"*)"##));
+        spec.rule(TokenRule::new(TokenType::CharLiteral, r"'([^'\\]|{escape})'|b'([^'\\\u{80}-\u{10FFFF}]|{byte_escape})'"));
+        spec.rule(TokenRule::new(TokenType::Invalid, r"b?'([^'\\]|\\(.|\n|x[0-9a-fA-F][0-9a-fA-F]|u\{[0-9a-fA-F_]*\}?))'"));
+        spec.rule(TokenRule::new(TokenType::Lifetime, r"'(_|{letter}){word_char}*"));
+
+        spec.rule(TokenRule::new(TokenType::Boolean, "true|false"));
+        spec.words(TokenType::KeyWord, &config.key_words);
+        let reserved = alternatives(&config.key_words.iter().map(String::as_str).chain(["true", "false"]).collect::<Vec<_>>());
+        spec.rule(TokenRule::new(TokenType::Macro, "{word}!").except(&format!("({})!", reserved)).not_before("[=]"));
+        spec.words(TokenType::DataType, &config.data_types);
+        spec.words(TokenType::Function, &config.functions);
+        match options.identifiers {
+            IdentifierMode::Restricted => {
+                let alphabet = identifier_alphabet(&config.identifier_symbols)?;
+                let start = alphabet.intersect(&class(&letter)?);
+                let rest = alphabet.intersect(&class(&word_char)?);
+                let mut starts = Vec::new();
+                if start != CharSet::default() {
+                    starts.push(start.to_pattern());
+                }
+                if alphabet.contains('_') && rest != CharSet::default() {
+                    starts.push(format!("_{}", rest.to_pattern()));
+                }
+                if !starts.is_empty() {
+                    spec.rule(TokenRule::new(TokenType::Identifier, &format!("({}){}*", starts.join("|"), rest.to_pattern())));
+                }
+                spec.rule(TokenRule::new(TokenType::Invalid, "{word}"));
+            }
+            IdentifierMode::Unicode => spec.rule(TokenRule::new(TokenType::Identifier, "{word}")),
+        }
+
+        let int_suffix = if integer_types.is_empty() { "" } else { "({int_suffix})?" };
+        let float_suffix = if float_types.is_empty() { "" } else { "({float_suffix})?" };
+        let fraction_end = CharSet::from_predicate(|c| c == '.' || c == '_' || c.is_alphabetic()).to_pattern();
+        for signed in [false, true] {
+            let sign = if signed { "[-+]" } else { "" };
+            let rule = |kind, pattern: String| {
+                let rule = TokenRule::new(kind, &format!("{}{}", sign, pattern));
+                if signed { rule.not_after("[0-9]") } else { rule }
+            };
+            spec.rule(rule(TokenType::Double, r"{digits}\.".to_string()).not_before(&fraction_end));
+            spec.rule(rule(TokenType::Double, format!(r"{{digits}}\.[0-9][0-9_]*({{exponent}})?{}", float_suffix)));
+            spec.rule(rule(TokenType::Double, format!("{{digits}}{{exponent}}{}", float_suffix)));
+            if !float_types.is_empty() {
+                spec.rule(rule(TokenType::Double, "{digits}({float_suffix})".to_string()));
+            }
+            spec.rule(rule(TokenType::Integer, format!("{{digits}}{}", int_suffix)));
+            spec.rule(rule(TokenType::Integer, format!("0x[0-9a-fA-F_]+{}", int_suffix)));
+            spec.rule(rule(TokenType::Integer, format!("0o[0-7_]+{}", int_suffix)));
+            spec.rule(rule(TokenType::Integer, format!("0b[01_]+{}", int_suffix)));
+            spec.rule(rule(TokenType::Invalid, r"{digits}\.[0-9][0-9_]*\.[0-9][0-9._]*".to_string()));
+            spec.rule(rule(TokenType::Invalid, r"({digits}(\.[0-9][0-9_]*)?({exponent})?|0x[0-9a-fA-F_]*|0o[0-7_]*|0b[01_]*){alnum}+".to_string()));
+        }
+
+        spec.symbols(TokenType::Arithmetic, &ARITHMETIC_SYMBOLS);
+        spec.symbols(TokenType::Comparison, &COMPARISON_SYMBOLS);
+        spec.symbols(TokenType::Logical, &LOGICAL_SYMBOLS);
+        spec.symbols(TokenType::Assignment, &ASSIGNMENT_SYMBOLS);
+        spec.symbols(TokenType::Delimiter, &DELIMITER_SYMBOLS);
+        spec.symbols(TokenType::Attribute, &ATTRIBUTE_SYMBOLS);
+        Ok(spec)
+    }
+
+    fn define(&mut self, name: &str, pattern: &str) {
+        self.definitions.insert(name.to_string(), pattern.to_string());
+    }
+
+    fn rule(&mut self, rule: TokenRule) {
+        self.rules.push(rule);
+    }
+
+    fn words(&mut self, kind: TokenType, words: &[String]) {
+        if !words.is_empty() {
+            self.rule(TokenRule::new(kind, &alternatives(words)));
+        }
+    }
+
+    fn symbols(&mut self, kind: TokenType, symbols: &[&str]) {
+        if !symbols.is_empty() {
+            self.rule(TokenRule::new(kind, &alternatives(symbols)));
+        }
+    }
+}
+
+fn alternatives<S: AsRef<str>>(words: &[S]) -> String {
+    words.iter().map(|w| escape_literal(w.as_ref())).collect::<Vec<_>>().join("|")
+}
+
+fn class(pattern: &str) -> Result<CharSet, SpecError> {
+    parse_class(pattern, &BTreeMap::new()).map_err(|message| SpecError::InvalidPattern { rule: "identifier classes".to_string(), message })
+}
+
+fn identifier_alphabet(symbols: &str) -> Result<CharSet, SpecError> {
+    let body = symbols.strip_prefix('^').unwrap_or(symbols);
+    let body = body.strip_suffix('$').unwrap_or(body);
+    let body = body.strip_suffix('+').or_else(|| body.strip_suffix('*')).unwrap_or(body);
+    parse_class(body, &BTreeMap::new()).map_err(|message| SpecError::InvalidPattern { rule: "identifier_symbols".to_string(), message })
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::token::{Token, TokenType};
+
+const BRANCH_KEYWORDS: [&str; 4] = ["if", "while", "for", "loop"];
+const BRANCH_OPERATORS: [&str; 2] = ["&&", "||"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SourceStats {
+    pub lines: usize,
+    pub tokens: usize,
+    pub invalid: usize,
+    pub invalid_ratio: f64,
+    pub max_depth: usize,
+    pub complexity: usize,
+    pub kinds: BTreeMap<TokenType, usize>,
+    pub keywords: BTreeMap<String, usize>,
+    pub identifiers: BTreeMap<String, usize>,
+}
+
+impl SourceStats {
+    pub fn identifiers_by_frequency(&self) -> Vec<(&str, usize)> {
+        let mut identifiers: Vec<(&str, usize)> = self.identifiers.iter().map(|(name, count)| (name.as_str(), *count)).collect();
+        identifiers.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        identifiers
+    }
+}
+
+pub fn source_stats(source: &str, tokens: &[Token]) -> SourceStats {
+    let mut stats = SourceStats { lines: source.lines().count(), complexity: 1, ..SourceStats::default() };
+    let mut depth = 0usize;
+    for token in tokens.iter().filter(|t| !matches!(t.type_name, TokenType::Whitespace | TokenType::Newline)) {
+        stats.tokens += 1;
+        *stats.kinds.entry(token.type_name).or_default() += 1;
+        match token.type_name {
+            TokenType::KeyWord => {
+                *stats.keywords.entry(token.content.clone()).or_default() += 1;
+                if BRANCH_KEYWORDS.contains(&token.content.as_str()) {
+                    stats.complexity += 1;
+                }
+            }
+            TokenType::Identifier => *stats.identifiers.entry(token.content.clone()).or_default() += 1,
+            TokenType::Logical if BRANCH_OPERATORS.contains(&token.content.as_str()) => stats.complexity += 1,
+            TokenType::Delimiter if token.content == "{" => {
+                depth += 1;
+                stats.max_depth = stats.max_depth.max(depth);
+            }
+            TokenType::Delimiter if token.content == "}" => depth = depth.saturating_sub(1),
+            TokenType::Invalid => stats.invalid += 1,
+            _ => {}
+        }
+    }
+    if stats.tokens > 0 {
+        stats.invalid_ratio = stats.invalid as f64 / stats.tokens as f64;
+    }
+    stats
+}
@@ -0,0 +1,215 @@
+use std::io::{self, ErrorKind, Read};
+use std::mem;
+use std::str;
+use crate::config::{Lexicon, DEFAULT_LEXICON};
+use crate::confusables::Skeletons;
+use crate::error::LexError;
+use crate::lexer::{Lexer, LexerOptions};
+use crate::token::{Position, Span, Token, TokenType};
+
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+pub const DEFAULT_MAX_TOKEN_LEN: usize = 1024 * 1024;
+pub const DEFAULT_MAX_IDENTIFIERS: usize = 64 * 1024;
+
+pub struct StreamLexer<'a, R> {
+    reader: R,
+    lexicon: &'a Lexicon,
+    options: LexerOptions,
+    chunk_size: usize,
+    max_token_len: usize,
+    buffer: String,
+    pending: Vec<u8>,
+    base: usize,
+    position: Position,
+    skeletons: Skeletons,
+    diagnostics: Vec<LexError>,
+    eof: bool,
+}
+
+impl<R: Read> StreamLexer<'static, R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, LexerOptions::default())
+    }
+
+    pub fn with_options(reader: R, options: LexerOptions) -> Self {
+        Self::with_lexicon(reader, &DEFAULT_LEXICON, options)
+    }
+}
+
+impl<'a, R: Read> StreamLexer<'a, R> {
+    pub fn with_lexicon(reader: R, lexicon: &'a Lexicon, options: LexerOptions) -> Self {
+        StreamLexer {
+            reader,
+            lexicon,
+            options,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_token_len: DEFAULT_MAX_TOKEN_LEN,
+            buffer: String::new(),
+            pending: Vec::new(),
+            base: 0,
+            position: Position { offset: 0, line: 1, column: 1 },
+            skeletons: Skeletons::with_limit(DEFAULT_MAX_IDENTIFIERS),
+            diagnostics: Vec::new(),
+            eof: false,
+        }
+    }
+
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn max_token_len(mut self, max_token_len: usize) -> Self {
+        self.max_token_len = max_token_len.max(4);
+        self
+    }
+
+    pub fn max_identifiers(mut self, max_identifiers: usize) -> Self {
+        self.skeletons = Skeletons::with_limit(max_identifiers);
+        self
+    }
+
+    pub fn take_diagnostics(&mut self) -> Vec<LexError> {
+        mem::take(&mut self.diagnostics)
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() + self.pending.len()
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let mut chunk = vec![0; self.chunk_size.max(self.buffer.len() - self.position.offset)];
+        let read = loop {
+            match self.reader.read(&mut chunk) {
+                Ok(read) => break read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        if read == 0 {
+            self.eof = true;
+            if !self.pending.is_empty() {
+                return Err(io::Error::new(ErrorKind::InvalidData, "stream ends inside a UTF-8 sequence"));
+            }
+            return Ok(());
+        }
+
+        self.pending.extend_from_slice(&chunk[..read]);
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(ErrorKind::InvalidData, e)),
+        };
+        self.buffer.push_str(str::from_utf8(&self.pending[..valid]).unwrap());
+        self.pending.drain(..valid);
+        Ok(())
+    }
+
+    fn compact(&mut self) {
+        if self.position.offset < self.chunk_size {
+            return;
+        }
+        let keep = self.buffer[..self.position.offset].char_indices().next_back().map_or(0, |(i, _)| i);
+        self.buffer.drain(..keep);
+        self.base += keep;
+        self.position.offset -= keep;
+    }
+
+    fn shift(&self, span: Span) -> Span {
+        Span { start: span.start + self.base, end: span.end + self.base, ..span }
+    }
+
+    fn next_token(&mut self) -> io::Result<Option<Token>> {
+        loop {
+            let mut lexer = Lexer::with_lexicon(&self.buffer, self.lexicon, self.options);
+            lexer.restore(self.position);
+            let result = lexer.next();
+            let (reach, end, warnings) = (lexer.reach(), lexer.location(), lexer.take_warnings());
+            drop(lexer);
+            if !self.eof && (result.is_none() || reach > self.buffer.len()) {
+                let start = match &result {
+                    Some(Ok(token)) => Position { offset: token.span.start, line: token.span.line, column: token.span.column },
+                    Some(Err(error)) => Position { offset: error.span().start, line: error.span().line, column: error.span().column },
+                    None => self.skip_trivia(),
+                };
+                if self.buffer.len() - start.offset >= self.max_token_len {
+                    return Ok(Some(self.oversized_token(start)));
+                }
+                self.fill()?;
+                continue;
+            }
+            let Some(result) = result else { return Ok(None) };
+
+            self.position = end;
+            for warning in warnings {
+                let span = self.shift(warning.span());
+                self.diagnostics.push(warning.with_span(span));
+            }
+            let token = match result {
+                Ok(mut token) => {
+                    token.span = self.shift(token.span);
+                    if token.type_name == TokenType::Identifier {
+                        if let Some(other) = self.skeletons.confusable_with(&token.content) {
+                            let name = token.content.clone();
+                            self.diagnostics.push(LexError::ConfusableIdentifier { span: token.span, name, other });
+                        }
+                    }
+                    token
+                }
+                Err(error) => {
+                    let span = error.span();
+                    let mut token = Token::new(TokenType::Invalid, self.buffer[span.start..span.end].to_string());
+                    token.span = self.shift(span);
+                    self.diagnostics.push(error.with_span(token.span));
+                    token
+                }
+            };
+            self.compact();
+            return Ok(Some(token));
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Position {
+        let options = LexerOptions { trivia: true, ..self.options };
+        let mut lexer = Lexer::with_lexicon(&self.buffer, self.lexicon, options);
+        lexer.restore(self.position);
+        let mut last = self.position;
+        loop {
+            let start = lexer.location();
+            if lexer.next().is_none() {
+                break;
+            }
+            last = start;
+        }
+        self.position = last;
+        self.compact();
+        self.position
+    }
+
+    fn oversized_token(&mut self, start: Position) -> Token {
+        let mut end = start.offset + self.max_token_len;
+        while !self.buffer.is_char_boundary(end) {
+            end -= 1;
+        }
+        let text = &self.buffer[start.offset..end];
+        let column = match text.rfind('\n') {
+            Some(newline) => text[newline + 1..].chars().count() + 1,
+            None => start.column + text.chars().count(),
+        };
+        let position = Position { offset: end, line: start.line + text.matches('\n').count(), column };
+        let mut token = Token::new(TokenType::Invalid, text.to_string());
+        token.span = self.shift(Span::new(start, position));
+        self.diagnostics.push(LexError::TokenTooLong { span: token.span, limit: self.max_token_len });
+        self.position = position;
+        self.compact();
+        token
+    }
+}
+
+impl<R: Read> Iterator for StreamLexer<'_, R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token().transpose()
+    }
+}
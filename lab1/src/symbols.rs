@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+use crate::ast::*;
+use crate::error::SymbolError;
+use crate::token::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    Function,
+    Struct,
+    Field,
+    Enum,
+    Variant,
+    Import,
+    Macro,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub ty: Option<String>,
+    pub mutable: bool,
+    pub scope: usize,
+    pub span: Span,
+    pub usages: Vec<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope {
+    pub parent: Option<usize>,
+    pub depth: usize,
+    pub span: Span,
+    pub symbols: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    pub scopes: Vec<Scope>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn lookup(&self, scope: usize, name: &str) -> Option<usize> {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let scope = &self.scopes[id];
+            if let Some(&symbol) = scope.symbols.iter().rev().find(|&&s| self.symbols[s].name == name) {
+                return Some(symbol);
+            }
+            current = scope.parent;
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolveOutput {
+    pub table: SymbolTable,
+    pub diagnostics: Vec<SymbolError>,
+}
+
+pub fn resolve(program: &Program) -> ResolveOutput {
+    let mut resolver = Resolver::default();
+    resolver.resolve_program(program);
+    ResolveOutput { table: resolver.table, diagnostics: resolver.diagnostics }
+}
+
+#[derive(Default)]
+struct Resolver {
+    table: SymbolTable,
+    current: usize,
+    members: HashMap<usize, Vec<usize>>,
+    impl_scopes: HashMap<usize, usize>,
+    diagnostics: Vec<SymbolError>,
+}
+
+impl Resolver {
+    fn enter_scope(&mut self, span: Span) -> usize {
+        let parent = self.table.scopes.get(self.current).map(|_| self.current);
+        let depth = parent.map_or(0, |p| self.table.scopes[p].depth + 1);
+        self.table.scopes.push(Scope { parent, depth, span, symbols: Vec::new() });
+        self.current = self.table.scopes.len() - 1;
+        self.current
+    }
+
+    fn exit_scope(&mut self) {
+        if let Some(parent) = self.table.scopes[self.current].parent {
+            self.current = parent;
+        }
+    }
+
+    fn declare(&mut self, name: &Ident, kind: SymbolKind, ty: Option<&Type>, mutable: bool) -> usize {
+        let symbol = Symbol {
+            name: name.name.clone(),
+            kind,
+            ty: ty.map(Type::to_string),
+            mutable,
+            scope: self.current,
+            span: name.span,
+            usages: Vec::new(),
+        };
+        self.table.symbols.push(symbol);
+        let id = self.table.symbols.len() - 1;
+        self.table.scopes[self.current].symbols.push(id);
+        id
+    }
+
+    fn use_symbol(&mut self, symbol: usize, span: Span) {
+        self.table.symbols[symbol].usages.push(span);
+    }
+
+    fn use_members(&mut self, kind: SymbolKind, name: &Ident) {
+        for symbol in self.table.symbols.iter_mut().filter(|s| s.kind == kind && s.name == name.name) {
+            symbol.usages.push(name.span);
+        }
+    }
+
+    fn lookup_member(&self, owner: usize, name: &str) -> Option<usize> {
+        self.members
+            .get(&owner)?
+            .iter()
+            .flat_map(|&scope| self.table.scopes[scope].symbols.iter().copied())
+            .find(|&s| self.table.symbols[s].name == name)
+    }
+
+    fn resolve_program(&mut self, program: &Program) {
+        self.enter_scope(program.span);
+        self.declare_items(program.items.iter());
+        for item in &program.items {
+            self.resolve_item(item);
+        }
+
+        let mut unused: Vec<SymbolError> = self
+            .table
+            .symbols
+            .iter()
+            .filter(|s| matches!(s.kind, SymbolKind::Variable | SymbolKind::Parameter))
+            .filter(|s| s.usages.is_empty() && s.name != "self" && !s.name.starts_with('_'))
+            .map(|s| SymbolError::Unused { span: s.span, name: s.name.clone() })
+            .collect();
+        unused.sort_by_key(|e| e.span().start);
+        self.diagnostics.extend(unused);
+    }
+
+    fn declare_items<'a>(&mut self, items: impl Iterator<Item = &'a Item> + Clone) {
+        for item in items.clone() {
+            match &item.kind {
+                ItemKind::Function(function) => {
+                    self.declare(&function.name, SymbolKind::Function, function.return_type.as_ref(), false);
+                }
+                ItemKind::Struct(structure) => {
+                    let owner = self.declare(&structure.name, SymbolKind::Struct, None, false);
+                    let scope = self.enter_scope(item.span);
+                    if let Fields::Named(fields) = &structure.fields {
+                        for field in fields {
+                            self.declare(&field.name, SymbolKind::Field, Some(&field.ty), false);
+                        }
+                    }
+                    self.exit_scope();
+                    self.members.entry(owner).or_default().push(scope);
+                }
+                ItemKind::Enum(enumeration) => {
+                    let owner = self.declare(&enumeration.name, SymbolKind::Enum, None, false);
+                    let scope = self.enter_scope(item.span);
+                    for variant in &enumeration.variants {
+                        self.declare(&variant.name, SymbolKind::Variant, None, false);
+                    }
+                    self.exit_scope();
+                    self.members.entry(owner).or_default().push(scope);
+                }
+                ItemKind::Impl(implementation) => {
+                    let scope = self.enter_scope(item.span);
+                    for function in &implementation.functions {
+                        if let ItemKind::Function(function) = &function.kind {
+                            self.declare(&function.name, SymbolKind::Function, function.return_type.as_ref(), false);
+                        }
+                    }
+                    self.exit_scope();
+                    self.impl_scopes.insert(item.span.start, scope);
+                }
+                ItemKind::Macro(definition) => {
+                    self.declare(&definition.name, SymbolKind::Macro, None, false);
+                }
+                ItemKind::Use(import) => {
+                    if !import.glob {
+                        if let Some(last) = import.path.segments.last() {
+                            self.declare(last, SymbolKind::Import, None, false);
+                        }
+                    }
+                }
+            }
+        }
+
+        for item in items {
+            if let ItemKind::Impl(implementation) = &item.kind {
+                if let Some(owner) = self.type_symbol(&implementation.self_type) {
+                    let scope = self.impl_scopes[&item.span.start];
+                    self.members.entry(owner).or_default().push(scope);
+                }
+            }
+        }
+    }
+
+    fn resolve_item(&mut self, item: &Item) {
+        match &item.kind {
+            ItemKind::Function(function) => {
+                self.enter_scope(item.span);
+                for param in &function.params {
+                    if let Some(ty) = &param.ty {
+                        self.resolve_type(ty);
+                    }
+                    self.declare(&param.name, SymbolKind::Parameter, param.ty.as_ref(), param.mutable);
+                }
+                if let Some(ty) = &function.return_type {
+                    self.resolve_type(ty);
+                }
+                self.resolve_block(&function.body);
+                self.exit_scope();
+            }
+            ItemKind::Struct(structure) => self.resolve_fields(&structure.fields),
+            ItemKind::Enum(enumeration) => {
+                for variant in &enumeration.variants {
+                    self.resolve_fields(&variant.fields);
+                }
+            }
+            ItemKind::Impl(implementation) => {
+                self.resolve_type(&implementation.self_type);
+                let parent = self.current;
+                if let Some(&scope) = self.impl_scopes.get(&item.span.start) {
+                    self.current = scope;
+                }
+                for function in &implementation.functions {
+                    self.resolve_item(function);
+                }
+                self.current = parent;
+            }
+            ItemKind::Use(_) | ItemKind::Macro(_) => {}
+        }
+    }
+
+    fn resolve_fields(&mut self, fields: &Fields) {
+        match fields {
+            Fields::Unit => {}
+            Fields::Tuple(types) => types.iter().for_each(|ty| self.resolve_type(ty)),
+            Fields::Named(fields) => fields.iter().for_each(|field| self.resolve_type(&field.ty)),
+        }
+    }
+
+    fn type_symbol(&self, ty: &Type) -> Option<usize> {
+        match &ty.kind {
+            TypeKind::Path { path, .. } if path.segments.len() == 1 => {
+                self.table.lookup(self.current, &path.segments[0].name)
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve_type(&mut self, ty: &Type) {
+        match &ty.kind {
+            TypeKind::Path { path, generics } => {
+                if let Some(symbol) = self.type_symbol(ty) {
+                    self.use_symbol(symbol, path.span);
+                }
+                generics.iter().for_each(|ty| self.resolve_type(ty));
+            }
+            TypeKind::Reference { inner, .. } => self.resolve_type(inner),
+            TypeKind::Array { element, length } => {
+                self.resolve_type(element);
+                if let Some(length) = length {
+                    self.resolve_expr(length);
+                }
+            }
+            TypeKind::Tuple(elements) => elements.iter().for_each(|ty| self.resolve_type(ty)),
+        }
+    }
+
+    fn resolve_block(&mut self, block: &Block) {
+        self.enter_scope(block.span);
+        self.declare_items(block.statements.iter().filter_map(|statement| match &statement.kind {
+            StmtKind::Item(item) => Some(item),
+            _ => None,
+        }));
+
+        for statement in &block.statements {
+            match &statement.kind {
+                StmtKind::Let { mutable, name, ty, init } => {
+                    if let Some(init) = init {
+                        self.resolve_expr(init);
+                    }
+                    if let Some(ty) = ty {
+                        self.resolve_type(ty);
+                    }
+                    self.declare(name, SymbolKind::Variable, ty.as_ref(), *mutable);
+                }
+                StmtKind::Item(item) => self.resolve_item(item),
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => self.resolve_expr(expr),
+            }
+        }
+        if let Some(tail) = &block.tail {
+            self.resolve_expr(tail);
+        }
+        self.exit_scope();
+    }
+
+    fn resolve_path(&mut self, path: &Path) -> Option<usize> {
+        let first = &path.segments[0];
+        let symbol = self.table.lookup(self.current, &first.name);
+        match symbol {
+            Some(symbol) => self.use_symbol(symbol, first.span),
+            None if path.segments.len() == 1 => {
+                self.diagnostics.push(SymbolError::Undeclared { span: first.span, name: first.name.clone() });
+                return None;
+            }
+            None => return None,
+        }
+
+        let mut owner = symbol?;
+        for segment in &path.segments[1..] {
+            owner = self.lookup_member(owner, &segment.name)?;
+            self.use_symbol(owner, segment.span);
+        }
+        Some(owner)
+    }
+
+    fn resolve_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Literal(_) | ExprKind::Bool(_) | ExprKind::Continue { .. } | ExprKind::Error => {}
+            ExprKind::Path(path) => {
+                self.resolve_path(path);
+            }
+            ExprKind::Unary { operand, .. } => self.resolve_expr(operand),
+            ExprKind::Binary { lhs, rhs, .. } => {
+                self.resolve_expr(lhs);
+                self.resolve_expr(rhs);
+            }
+            ExprKind::Assign { target, value, .. } => {
+                self.resolve_expr(value);
+                self.resolve_expr(target);
+            }
+            ExprKind::Range { start, end, .. } => {
+                start.iter().chain(end.iter()).for_each(|e| self.resolve_expr(e));
+            }
+            ExprKind::Call { callee, args } => {
+                self.resolve_expr(callee);
+                args.iter().for_each(|e| self.resolve_expr(e));
+            }
+            ExprKind::BuiltinCall { args, .. } => args.iter().for_each(|e| self.resolve_expr(e)),
+            ExprKind::MacroCall { name, args } => {
+                if let Some(symbol) = self.table.lookup(self.current, &name.name) {
+                    self.use_symbol(symbol, name.span);
+                }
+                args.iter().for_each(|e| self.resolve_expr(e));
+            }
+            ExprKind::MethodCall { receiver, method, args } => {
+                self.resolve_expr(receiver);
+                self.use_members(SymbolKind::Function, method);
+                args.iter().for_each(|e| self.resolve_expr(e));
+            }
+            ExprKind::Field { base, field } => {
+                self.resolve_expr(base);
+                self.use_members(SymbolKind::Field, field);
+            }
+            ExprKind::Index { base, index } => {
+                self.resolve_expr(base);
+                self.resolve_expr(index);
+            }
+            ExprKind::Try(inner) | ExprKind::Paren(inner) => self.resolve_expr(inner),
+            ExprKind::Break { value, .. } | ExprKind::Return(value) => value.iter().for_each(|e| self.resolve_expr(e)),
+            ExprKind::StructLiteral { path, fields } => {
+                let owner = self.resolve_path(path);
+                for (name, value) in fields {
+                    if let Some(field) = owner.and_then(|owner| self.lookup_member(owner, &name.name)) {
+                        self.use_symbol(field, name.span);
+                    }
+                    self.resolve_expr(value);
+                }
+            }
+            ExprKind::Array(elements) | ExprKind::Tuple(elements) => elements.iter().for_each(|e| self.resolve_expr(e)),
+            ExprKind::Block(block) | ExprKind::Loop { body: block, .. } => self.resolve_block(block),
+            ExprKind::If { condition, then_branch, else_branch } => {
+                self.resolve_expr(condition);
+                self.resolve_block(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.resolve_expr(else_branch);
+                }
+            }
+            ExprKind::While { condition, body, .. } => {
+                self.resolve_expr(condition);
+                self.resolve_block(body);
+            }
+            ExprKind::For { binding, iterable, body, .. } => {
+                self.resolve_expr(iterable);
+                self.enter_scope(expr.span);
+                self.declare(binding, SymbolKind::Variable, None, false);
+                self.resolve_block(body);
+                self.exit_scope();
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TokenType {
+    DataType,
+    Integer,
+    Double,
+    Identifier,
+    Arithmetic,
+    Function,
+    KeyWord,
+    Delimiter,
+    Comparison,
+    Logical,
+    Assignment,
+    Boolean,
+    StringLiteral,
+    CharLiteral,
+    Comment,
+    Lifetime,
+    Attribute,
+    Macro,
+    Whitespace,
+    Newline,
+    Invalid,
+}
+
+impl fmt::Display for TokenType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Span {
+        Span {
+            start: start.offset,
+            end: end.offset,
+            line: start.line,
+            column: start.column,
+        }
+    }
+
+    pub fn to(self, end: Span) -> Span {
+        Span { end: end.end.max(self.end), ..self }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralValue {
+    Str(String),
+    ByteStr(Vec<u8>),
+    Char(char),
+    Byte(u8),
+    Integer { value: u128, negative: bool, suffix: Option<String> },
+    Float { value: f64, suffix: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub type_name: TokenType,
+    pub content: String,
+    pub span: Span,
+    pub value: Option<LiteralValue>,
+}
+
+impl Token {
+    pub fn new(type_name: TokenType, content: String) -> Token {
+        Token { type_name, content, span: Span::default(), value: None }
+    }
+
+    pub fn literal(type_name: TokenType, content: String, value: LiteralValue) -> Token {
+        Token { type_name, content, span: Span::default(), value: Some(value) }
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        matches!(self.type_name, TokenType::Whitespace | TokenType::Newline | TokenType::Comment)
+    }
+
+    pub fn is_doc_comment(&self) -> bool {
+        self.type_name == TokenType::Comment
+            && ((self.content.starts_with("///") && !self.content.starts_with("////"))
+                || self.content.starts_with("//!")
+                || (self.content.starts_with("/**") && !self.content.starts_with("/***") && self.content != "/**/")
+                || self.content.starts_with("/*!"))
+    }
+}
@@ -0,0 +1,85 @@
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+fn lab1(args: &[&str], stdin: &str) -> (String, String, Option<i32>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lab1"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap(), output.status.code())
+}
+
+fn temp_source(name: &str, source: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("lab1-cli-{}-{}", std::process::id(), name));
+    fs::write(&path, source).unwrap();
+    path
+}
+
+#[test]
+fn stdin_is_read_without_paths() {
+    let (stdout, stderr, code) = lab1(&[], "shvachka = 1;");
+    assert_eq!(code, Some(0), "{}", stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "<stdin>");
+    assert!(lines[1].starts_with("Line:Col"));
+    assert_eq!(lines[2].split_whitespace().collect::<Vec<_>>(), ["1:1", "Identifier", "shvachka"]);
+    assert_eq!(lines[5].split_whitespace().collect::<Vec<_>>(), ["1:13", "Delimiter", ";"]);
+
+    let (dash, _, _) = lab1(&["-"], "shvachka = 1;");
+    assert_eq!(dash, stdout);
+}
+
+#[test]
+fn every_path_is_lexed_in_order() {
+    let first = temp_source("first.rs", "fn a() {}");
+    let second = temp_source("second.rs", "let h = 2;");
+    let (stdout, stderr, code) = lab1(&["--format", "json", first.to_str().unwrap(), second.to_str().unwrap()], "");
+    fs::remove_file(&first).unwrap();
+    fs::remove_file(&second).unwrap();
+    assert_eq!(code, Some(0), "{}", stderr);
+
+    let files: Vec<String> = stdout.lines().map(|line| serde_json::from_str::<Value>(line).unwrap()["file"].as_str().unwrap().to_string()).collect();
+    let (first, second) = (first.display().to_string(), second.display().to_string());
+    assert_eq!(files.iter().filter(|f| **f == first).count(), 6);
+    assert_eq!(files.iter().filter(|f| **f == second).count(), 5);
+    assert!(files.iter().rposition(|f| *f == first) < files.iter().position(|f| *f == second));
+}
+
+#[test]
+fn json_output_is_one_object_per_line() {
+    let (stdout, _, code) = lab1(&["--format", "json"], "let s = \"a\nb\";");
+    assert_eq!(code, Some(0));
+    let rows: Vec<Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(rows.len(), 5);
+    for row in &rows {
+        let keys: Vec<&String> = row.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["file", "kind", "span", "text"]);
+        assert_eq!(row["file"], "<stdin>");
+        let span: Vec<&String> = row["span"].as_object().unwrap().keys().collect();
+        assert_eq!(span, ["column", "end", "line", "start"]);
+    }
+    assert_eq!(rows[3]["kind"], "StringLiteral");
+    assert_eq!(rows[3]["text"], "\"a\nb\"");
+    assert_eq!((rows[4]["span"]["line"].as_u64(), rows[4]["span"]["column"].as_u64()), (Some(2), Some(3)));
+}
+
+#[test]
+fn invalid_tokens_fail_the_run() {
+    let (stdout, _, code) = lab1(&["--format", "json"], "shvachka $ 1");
+    assert_eq!(code, Some(1));
+    assert!(stdout.lines().any(|line| serde_json::from_str::<Value>(line).unwrap()["kind"] == "Invalid"));
+    assert_eq!(lab1(&[], "shvachka + 1").2, Some(0));
+
+    let missing = std::env::temp_dir().join("lab1-cli-missing.rs");
+    let (_, stderr, code) = lab1(&[missing.to_str().unwrap()], "");
+    assert_eq!(code, Some(2));
+    assert!(stderr.starts_with("Error: "), "{}", stderr);
+}
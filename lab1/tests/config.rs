@@ -0,0 +1,109 @@
+use lab1::{lex_with_lexicon, ConfigError, DfaLexer, LexerConfig, LexerOptions, LiteralValue, TokenSpec, TokenType};
+use std::fs;
+use std::path::PathBuf;
+
+const TOML: &str = r#"
+key_words = ["якщо", "let"]
+functions = ["друк"]
+data_types = ["ціле", "u8", "f64"]
+identifier_symbols = "^[a-zа-яі_]+$"
+"#;
+
+const JSON: &str = r#"{"key_words": ["якщо", "let"], "functions": ["друк"], "data_types": ["ціле", "u8", "f64"], "identifier_symbols": "^[a-zа-яі_]+$"}"#;
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("lab1-config-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn types(config: &LexerConfig, source: &str) -> Vec<(TokenType, String)> {
+    let lexicon = config.compile().unwrap();
+    lex_with_lexicon(source, &lexicon, LexerOptions::default()).tokens.into_iter().map(|t| (t.type_name, t.content)).collect()
+}
+
+#[test]
+fn toml_and_json_configs_agree() {
+    let from_toml = LexerConfig::from_toml(TOML).unwrap();
+    assert_eq!(from_toml, LexerConfig::from_json(JSON).unwrap());
+    assert_eq!(from_toml.data_types, ["ціле", "u8", "f64"]);
+    assert_eq!(from_toml.identifier_symbols, "^[a-zа-яі_]+$");
+}
+
+#[test]
+fn missing_fields_fall_back_to_defaults() {
+    let config = LexerConfig::from_toml("functions = [\"друк\"]").unwrap();
+    assert_eq!(config.functions, ["друк"]);
+    assert_eq!(config.key_words, LexerConfig::default().key_words);
+    assert_eq!(LexerConfig::from_json("{}").unwrap(), LexerConfig::default());
+}
+
+#[test]
+fn load_picks_the_format_from_the_extension() {
+    let toml = temp_file("lexer.toml", TOML);
+    let json = temp_file("lexer.json", JSON);
+    let yaml = temp_file("lexer.yaml", TOML);
+    assert_eq!(LexerConfig::load(&toml).unwrap(), LexerConfig::load(&json).unwrap());
+    let error = LexerConfig::load(&yaml).unwrap_err();
+    assert!(matches!(error, ConfigError::UnknownFormat(_)), "{:?}", error);
+    assert!(error.to_string().ends_with("(expected .toml or .json)"));
+    for path in [toml, json, yaml] {
+        fs::remove_file(path).unwrap();
+    }
+
+    let missing = std::env::temp_dir().join("lab1-config-missing.toml");
+    assert!(matches!(LexerConfig::load(&missing), Err(ConfigError::Io(_))));
+}
+
+#[test]
+fn malformed_configs_are_rejected() {
+    assert!(matches!(LexerConfig::from_toml("key_words = 5"), Err(ConfigError::Parse(_))));
+    assert!(matches!(LexerConfig::from_json("{\"key_words\": [1]}"), Err(ConfigError::Parse(_))));
+    let config = LexerConfig { identifier_symbols: "^[a-".to_string(), ..LexerConfig::default() };
+    let error = config.compile().unwrap_err();
+    assert!(error.to_string().starts_with("invalid identifier pattern: "), "{}", error);
+}
+
+#[test]
+fn compiled_lexicon_drives_the_lexer() {
+    let config = LexerConfig::from_toml(TOML).unwrap();
+    assert_eq!(
+        types(&config, "якщо друк(x) ціле if"),
+        [
+            (TokenType::KeyWord, "якщо".to_string()),
+            (TokenType::Function, "друк".to_string()),
+            (TokenType::Delimiter, "(".to_string()),
+            (TokenType::Identifier, "x".to_string()),
+            (TokenType::Delimiter, ")".to_string()),
+            (TokenType::DataType, "ціле".to_string()),
+            (TokenType::Identifier, "if".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn non_ascii_data_types_are_not_numeric_suffixes() {
+    let config = LexerConfig::from_toml(TOML).unwrap();
+    let lexicon = config.compile().unwrap();
+    let output = lex_with_lexicon("5ціле 7u8", &lexicon, LexerOptions::default());
+    assert_eq!(output.diagnostics.len(), 1);
+    assert_eq!(output.diagnostics[0].to_string(), "malformed number: invalid suffix `ціле` for number literal");
+    assert_eq!(output.tokens[1].value, Some(LiteralValue::Integer { value: 7, negative: false, suffix: Some("u8".to_string()) }));
+
+    let spec = TokenSpec::from_lexicon(&lexicon, LexerOptions::default()).unwrap();
+    assert!(spec.to_toml().contains("u8"));
+    let dfa = DfaLexer::with_lexicon(&lexicon, LexerOptions::default()).unwrap();
+    let tokens = dfa.tokenize("5ціле 7u8");
+    assert_eq!(tokens.last().map(|t| t.content.as_str()), Some("7u8"));
+}
+
+#[test]
+fn odd_data_type_names_do_not_panic() {
+    let config = LexerConfig { data_types: vec!["".to_string(), "i0".to_string(), "u".to_string(), "ф32".to_string()], ..LexerConfig::default() };
+    let lexicon = config.compile().unwrap();
+    for source in ["1i0", "1u", "1ф32"] {
+        let output = lex_with_lexicon(source, &lexicon, LexerOptions::default());
+        assert_eq!(output.diagnostics.len(), 1, "{}", source);
+    }
+    assert!(TokenSpec::from_lexicon(&lexicon, LexerOptions::default()).is_ok());
+}
@@ -0,0 +1,167 @@
+use lab1::{lex_with_options, DfaLexer, IdentifierMode, Lexicon, LexerOptions, SpecError, Token, TokenRule, TokenSpec, TokenType};
+use proptest::prelude::*;
+use std::sync::OnceLock;
+
+const SOURCE: &str = r##"#![allow(dead_code)]
+#[derive(Debug)]
+fn shvachka<'a>(a: f64, hvac: &'a i32) -> f64 {
+    // рядок коментаря з кирилицею
+    let mut sk = sqrt(a) * 2.5e-3 + -12 - 3;
+    let s = "рядок \u{1F600} з escape\n";
+    let r = r#"raw "text" ще"#;
+    let (c, b) = ('ш', b'\x7F');
+    /* nested /* block */ comment */
+    if sk >= 10 && hvac != 0 { sk += cos(a) / 3; } else { sk -= 0x1F_u8; }
+    let сk = 'a;
+    println!("{}", 1..=3, 1.5f32, 1.);
+    $ a!=b; fn! 1.2.3 7abc '\q' "bad \q" "end
+"##;
+
+const FRAGMENTS: [&str; 40] = [
+    "fn", "let", "mut", "if", "shva", "chka", "ск", "x", "_", "1", "25", "2.5", "1e", "0x1f", "+", "-", ".", "..", "=",
+    "!", "<", "'", "'a", "\"", "r", "b", "#", "\\", "{", "}", "(", ")", ";", "/", "*", "//", "/*", "*/", " ", "\n",
+];
+
+const MODES: [(bool, bool, bool); 4] = [(false, false, false), (true, false, false), (false, true, false), (false, false, true)];
+
+fn options(keep_comments: bool, unicode: bool, trivia: bool) -> LexerOptions {
+    let identifiers = if unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+    LexerOptions { keep_comments, identifiers, trivia }
+}
+
+fn shape(tokens: &[Token]) -> Vec<(TokenType, &str, usize, usize, usize)> {
+    tokens.iter().map(|t| (t.type_name, t.content.as_str(), t.span.start, t.span.line, t.span.column)).collect()
+}
+
+#[test]
+fn generated_dfa_matches_the_hand_written_lexer() {
+    for unicode in [false, true] {
+        for (keep_comments, trivia) in [(false, false), (true, false), (false, true)] {
+            let options = options(keep_comments, unicode, trivia);
+            let dfa = DfaLexer::with_lexicon(&Lexicon::default(), options).unwrap();
+            let expected = lex_with_options(SOURCE, options);
+            assert_eq!(shape(&dfa.tokenize(SOURCE)), shape(&expected.tokens), "{:?}", options);
+        }
+    }
+}
+
+#[test]
+fn spec_applies_longest_match_priority_and_context() {
+    let spec = TokenSpec::from_toml(
+        r#"
+        [definitions]
+        digit = "[0-9]"
+
+        [[rules]]
+        kind = "Whitespace"
+        pattern = '\s+'
+        skip = true
+
+        [[rules]]
+        kind = "KeyWord"
+        pattern = "if|iff"
+
+        [[rules]]
+        kind = "Macro"
+        pattern = "[a-z]+!"
+        except = "if!"
+        not_before = "[=]"
+
+        [[rules]]
+        kind = "Identifier"
+        pattern = "[a-z]+"
+
+        [[rules]]
+        kind = "Integer"
+        pattern = "-?{digit}+"
+        not_after = "[0-9a-z]"
+
+        [[rules]]
+        kind = "Integer"
+        pattern = "{digit}+"
+
+        [[rules]]
+        kind = "Arithmetic"
+        pattern = '-|!|!='
+        "#,
+    )
+    .unwrap();
+    let dfa = DfaLexer::compile(&spec).unwrap();
+    let tokens = dfa.tokenize("if iff ifx if! go! go!= -1 x-1 2-1 ?");
+    let kinds: Vec<(TokenType, &str)> = tokens.iter().map(|t| (t.type_name, t.content.as_str())).collect();
+    assert_eq!(
+        kinds,
+        [
+            (TokenType::KeyWord, "if"),
+            (TokenType::KeyWord, "iff"),
+            (TokenType::Identifier, "ifx"),
+            (TokenType::KeyWord, "if"),
+            (TokenType::Arithmetic, "!"),
+            (TokenType::Macro, "go!"),
+            (TokenType::Identifier, "go"),
+            (TokenType::Arithmetic, "!="),
+            (TokenType::Integer, "-1"),
+            (TokenType::Identifier, "x"),
+            (TokenType::Arithmetic, "-"),
+            (TokenType::Integer, "1"),
+            (TokenType::Integer, "2"),
+            (TokenType::Arithmetic, "-"),
+            (TokenType::Integer, "1"),
+            (TokenType::Invalid, "?"),
+        ]
+    );
+    assert_eq!((tokens[15].span.line, tokens[15].span.column), (1, 36));
+}
+
+#[test]
+fn dfa_is_minimised() {
+    let mut spec = TokenSpec::default();
+    spec.rules.push(TokenRule::new(TokenType::Identifier, "(a|b)*abb"));
+    let dfa = DfaLexer::compile(&spec).unwrap();
+    assert_eq!(dfa.states(), 5);
+    assert_eq!(dfa.classes(), 3);
+    assert_eq!(dfa.tokenize("babaabb").len(), 1);
+}
+
+#[test]
+fn spec_errors_name_the_rule() {
+    let check = |pattern: &str, message: &str| {
+        let mut spec = TokenSpec::default();
+        spec.rules.push(TokenRule::new(TokenType::Identifier, "x"));
+        spec.rules.push(TokenRule::new(TokenType::Integer, pattern));
+        match DfaLexer::compile(&spec) {
+            Err(error @ SpecError::InvalidPattern { .. }) => assert_eq!(error.to_string(), format!("invalid pattern in rule 1 (Integer): {}", message)),
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+    };
+    check("(1|2", "unclosed group");
+    check("[0-9]*", "pattern matches the empty string");
+    check("{number}", "unknown definition `number`");
+    check("[9-0]", "class range `9-0` is out of order");
+    check("+1", "nothing to repeat before `+`");
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn trivia_dfa_is_lossless(source in "(\\PC|\\s|\r\n){0,80}") {
+        static DFA: OnceLock<DfaLexer> = OnceLock::new();
+        let dfa = DFA.get_or_init(|| DfaLexer::with_lexicon(&Lexicon::default(), options(false, false, true)).unwrap());
+        let tokens = dfa.tokenize(&source);
+        prop_assert_eq!(tokens.iter().map(|t| t.content.as_str()).collect::<String>(), source);
+    }
+
+    #[test]
+    fn generated_dfa_agrees_on_fragments(parts in prop::collection::vec(prop::sample::select(&FRAGMENTS[..]), 0..40), mode in 0..MODES.len()) {
+        let source = parts.concat();
+        static DFAS: OnceLock<Vec<DfaLexer>> = OnceLock::new();
+        let dfas = DFAS.get_or_init(|| MODES.iter().map(|m| DfaLexer::with_lexicon(&Lexicon::default(), options(m.0, m.1, m.2)).unwrap()).collect());
+        let (keep_comments, unicode, trivia) = MODES[mode];
+        let options = options(keep_comments, unicode, trivia);
+        let dfa = &dfas[mode];
+        let expected = lex_with_options(&source, options);
+        let tokens = dfa.tokenize(&source);
+        prop_assert_eq!(shape(&tokens), shape(&expected.tokens), "source {:?}", source);
+    }
+}
@@ -0,0 +1,90 @@
+use lab1::{lex, Diagnostic, LexOutput};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn dump(output: &LexOutput) -> String {
+    let mut dump = format!("{:<10} {:<14} Text\n", "Line:Col", "Kind");
+    for token in &output.tokens {
+        let location = format!("{}:{}", token.span.line, token.span.column);
+        let text = token.content.replace('\n', "\\n").replace('\r', "\\r").replace('\t', "\\t");
+        dump.push_str(&format!("{:<10} {:<14} {}\n", location, token.type_name.to_string(), text));
+    }
+    dump.push_str(&format!("\nIdentifiers: {}\n", output.identifiers().join(", ")));
+    for error in &output.diagnostics {
+        let span = Diagnostic::span(error);
+        dump.push_str(&format!("{} {}:{}: {}\n", error.level(), span.line, span.column, error));
+    }
+    dump
+}
+
+fn print_diff(expected: &str, actual: &str) {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => {}
+            (e, a) => {
+                if let Some(e) = e {
+                    println!("  {:>4} - {}", i + 1, e);
+                }
+                if let Some(a) = a {
+                    println!("  {:>4} + {}", i + 1, a);
+                }
+            }
+        }
+    }
+}
+
+fn fixtures(dir: &Path, filters: &[String]) -> Vec<PathBuf> {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter(|path| filters.is_empty() || filters.iter().any(|f| path.to_string_lossy().contains(f.as_str())))
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let bless = args.iter().any(|arg| arg == "--bless") || env::var_os("BLESS").is_some();
+    let filters: Vec<String> = args.into_iter().filter(|arg| !arg.starts_with('-')).collect();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+
+    let fixtures = fixtures(&dir, &filters);
+    let mut failed = 0;
+    println!("\nrunning {} golden fixtures", fixtures.len());
+    for fixture in &fixtures {
+        let name = fixture.file_name().unwrap().to_string_lossy();
+        let input = fs::read_to_string(fixture).unwrap_or_else(|e| panic!("cannot read {}: {}", fixture.display(), e));
+        let actual = dump(&lex(&input));
+        let expected_path = fixture.with_extension("tokens");
+        let expected = fs::read_to_string(&expected_path).ok();
+
+        if expected.as_deref() == Some(actual.as_str()) {
+            println!("golden {} ... ok", name);
+        } else if bless {
+            fs::write(&expected_path, &actual).unwrap_or_else(|e| panic!("cannot write {}: {}", expected_path.display(), e));
+            println!("golden {} ... blessed", name);
+        } else {
+            failed += 1;
+            match &expected {
+                Some(expected) => {
+                    println!("golden {} ... FAILED", name);
+                    print_diff(expected, &actual);
+                }
+                None => println!("golden {} ... FAILED (missing {})", name, expected_path.display()),
+            }
+        }
+    }
+
+    println!("\ngolden result: {} passed; {} failed\n", fixtures.len() - failed, failed);
+    if failed > 0 {
+        println!("run `cargo test --test golden -- --bless` to accept the new output");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
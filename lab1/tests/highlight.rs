@@ -0,0 +1,75 @@
+use lab1::highlight::{ansi_color, css_class};
+use lab1::{lex, render_ansi, render_html, TokenType};
+
+const SOURCE: &str = "// швачка\nfn shvachka(s: i32) -> bool {\n    s < 10 && \"a&b\" != \"\" $\n}\n";
+
+fn pre_body(html: &str) -> &str {
+    let start = html.find("<pre class=\"lab1\">").unwrap() + "<pre class=\"lab1\">".len();
+    &html[start..html.rfind("</pre>").unwrap()]
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[test]
+fn html_escapes_markup_characters() {
+    let html = render_html(SOURCE, &lex(SOURCE).tokens, "a < b & \"c\"");
+    assert!(html.contains("<title>a &lt; b &amp; &quot;c&quot;</title>"), "{}", html);
+    let body = pre_body(&html);
+    assert!(body.contains(">&lt;</span>"), "{}", body);
+    assert!(body.contains(">&amp;&amp;</span>"), "{}", body);
+    assert!(body.contains(">&quot;a&amp;b&quot;</span>"), "{}", body);
+    assert!(!strip_tags(body).contains("&amp;"));
+}
+
+#[test]
+fn every_token_gets_one_span_with_class_and_title() {
+    let tokens = lex(SOURCE).tokens;
+    let body = pre_body(&render_html(SOURCE, &tokens, "t")).to_string();
+    assert_eq!(body.matches("<span ").count(), tokens.len());
+    for token in &tokens {
+        let text = token.content.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;");
+        let span = format!("<span class=\"{}\" title=\"{}\">{}</span>", css_class(token.type_name), token.type_name, text);
+        assert!(body.contains(&span), "{} not in {}", span, body);
+    }
+}
+
+#[test]
+fn invalid_tokens_are_marked() {
+    let tokens = lex(SOURCE).tokens;
+    assert!(tokens.iter().any(|t| t.type_name == TokenType::Invalid && t.content == "$"));
+    assert!(render_html(SOURCE, &tokens, "t").contains("<span class=\"tok-invalid\" title=\"Invalid\">$</span>"));
+    assert!(ansi_color(TokenType::Invalid).contains("41"));
+    assert!(render_ansi(SOURCE, &tokens).contains(&format!("{}$\x1b[0m", ansi_color(TokenType::Invalid))));
+}
+
+#[test]
+fn rendered_text_reconstructs_the_source() {
+    let tokens = lex(SOURCE).tokens;
+    assert_eq!(strip_tags(pre_body(&render_html(SOURCE, &tokens, "t"))), SOURCE);
+    assert_eq!(strip_ansi(&render_ansi(SOURCE, &tokens)), SOURCE);
+}
@@ -0,0 +1,119 @@
+use lab1::confusables::{mixed_script_confusables, script, skeleton, Script};
+use lab1::{lex_with_options, parse, resolve, DfaLexer, IdentifierMode, LexError, LexerOptions, Token, TokenType};
+
+fn unicode() -> LexerOptions {
+    LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() }
+}
+
+fn tokens(source: &str, options: LexerOptions) -> Vec<(TokenType, String)> {
+    let output = lex_with_options(source, options);
+    assert!(output.diagnostics.is_empty(), "{}: {:?}", source, output.diagnostics);
+    output.tokens.into_iter().map(|t| (t.type_name, t.content)).collect()
+}
+
+fn tokens_of(source: &str) -> Vec<Token> {
+    lex_with_options(source, unicode()).tokens
+}
+
+#[test]
+fn leading_underscore_belongs_to_the_identifier() {
+    for options in [LexerOptions::default(), unicode()] {
+        assert_eq!(
+            tokens("_shva __ _1 _ _chka!", options),
+            [
+                (TokenType::Identifier, "_shva".to_string()),
+                (TokenType::Identifier, "__".to_string()),
+                (TokenType::Identifier, "_1".to_string()),
+                (TokenType::Delimiter, "_".to_string()),
+                (TokenType::Macro, "_chka!".to_string()),
+            ]
+        );
+    }
+    assert_eq!(tokens("(_)", unicode())[1], (TokenType::Delimiter, "_".to_string()));
+    assert_eq!(tokens("_+x", unicode())[0], (TokenType::Delimiter, "_".to_string()));
+}
+
+#[test]
+fn dfa_agrees_on_underscore_identifiers() {
+    for options in [LexerOptions::default(), unicode()] {
+        let source = "let _shva = _; __ _1 _z _chka!";
+        let expected: Vec<_> = lex_with_options(source, options).tokens.into_iter().map(|t| (t.type_name, t.content)).collect();
+        let dfa = DfaLexer::with_lexicon(&Default::default(), options).unwrap();
+        let actual: Vec<_> = dfa.tokenize(source).into_iter().map(|t| (t.type_name, t.content)).collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn underscore_prefixed_bindings_are_not_reported_unused() {
+    let parsed = parse(&tokens_of("fn main() { let _shva = 1; let chka = 2; }"));
+    assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+    let output = resolve(&parsed.program);
+    let unused: Vec<String> = output.diagnostics.iter().map(|d| d.to_string()).collect();
+    assert_eq!(unused.len(), 1, "{:?}", unused);
+    assert!(unused[0].contains("chka"), "{:?}", unused);
+}
+
+fn warnings(source: &str) -> Vec<String> {
+    let output = lex_with_options(source, unicode());
+    assert!(output.diagnostics.iter().all(LexError::is_warning), "{:?}", output.diagnostics);
+    output.diagnostics.iter().map(LexError::to_string).collect()
+}
+
+#[test]
+fn unicode_mode_accepts_any_xid_identifier() {
+    assert_eq!(
+        tokens("größe λ_1 naïve 変数", unicode()),
+        [
+            (TokenType::Identifier, "größe".to_string()),
+            (TokenType::Identifier, "λ_1".to_string()),
+            (TokenType::Identifier, "naïve".to_string()),
+            (TokenType::Identifier, "変数".to_string()),
+        ]
+    );
+    let restricted = lex_with_options("größe", LexerOptions::default());
+    assert!(restricted.tokens.iter().all(|t| t.type_name == TokenType::Invalid), "{:?}", restricted.tokens);
+    assert!(!restricted.diagnostics.is_empty());
+}
+
+#[test]
+fn identifiers_must_start_with_xid_start() {
+    let output = lex_with_options("\u{301}x", unicode());
+    let tokens: Vec<(TokenType, &str)> = output.tokens.iter().map(|t| (t.type_name, t.content.as_str())).collect();
+    assert_eq!(tokens, [(TokenType::Invalid, "\u{301}"), (TokenType::Identifier, "x")]);
+}
+
+#[test]
+fn identifiers_are_nfc_normalised() {
+    let decomposed = "cafe\u{301}";
+    let output = lex_with_options(decomposed, unicode());
+    assert_eq!(output.tokens.len(), 1);
+    assert_eq!(output.tokens[0].content, "caf\u{e9}");
+    assert_eq!((output.tokens[0].span.start, output.tokens[0].span.end), (0, decomposed.len()));
+    assert!(output.diagnostics.is_empty());
+}
+
+#[test]
+fn mixed_script_identifiers_warn() {
+    assert_eq!(warnings("p\u{430}ssword"), ["identifier contains `\u{430}` (U+0430), which looks like `a` (U+0061)"]);
+    assert_eq!(warnings("шв\u{61}чка"), ["identifier contains `a` (U+0061), which looks like `\u{430}` (U+0430)"]);
+    assert!(warnings("швачка shvachka λόγος").is_empty());
+}
+
+#[test]
+fn confusable_identifiers_warn_on_the_later_one() {
+    let source = "scope + \u{455}\u{441}\u{43e}\u{440}\u{435} + scope";
+    let output = lex_with_options(source, unicode());
+    assert_eq!(output.diagnostics.len(), 1, "{:?}", output.diagnostics);
+    assert_eq!(output.diagnostics[0].to_string(), "identifier `\u{455}\u{441}\u{43e}\u{440}\u{435}` is confusable with `scope`");
+    assert_eq!(output.diagnostics[0].span(), output.tokens[2].span);
+    assert!(output.diagnostics[0].is_warning());
+}
+
+#[test]
+fn confusable_tables() {
+    assert_eq!(skeleton("\u{455}\u{441}\u{43e}\u{440}\u{435}"), "scope");
+    assert_eq!((script('a'), script('ш'), script('λ'), script('1')), (Script::Latin, Script::Cyrillic, Script::Greek, Script::Other));
+    assert_eq!(mixed_script_confusables("x\u{43e}r"), [('\u{43e}', 'o')]);
+    assert!(mixed_script_confusables("_1").is_empty());
+}
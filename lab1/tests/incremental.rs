@@ -0,0 +1,70 @@
+use lab1::{lex_with_options, relex_with_options, EditError, IdentifierMode, LexerOptions, TextEdit};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use std::ops::Range;
+
+const FRAGMENTS: [&str; 48] = [
+    "fn", "let", "mut", "if", "shva", "chka", "ск", "sкa", "x", "_", "1", "25", "2.5", "1e", "0x1f", "+", "-", ".", "..",
+    "=", "!", "<", "'", "'a", "\"", "r", "b", "#", "\\", "u{", "{", "}", "(", ")", ";", ",", "/", "*", "//", "/*", "*/",
+    "vasya", "sqrt", "i32", " ", " ", "\n", "\t",
+];
+
+fn fragments(max: usize) -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(&FRAGMENTS[..]), 0..max).prop_map(|parts| parts.concat())
+}
+
+fn char_boundary(source: &str, mut offset: usize) -> usize {
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn options(keep_comments: bool, unicode: bool, trivia: bool) -> LexerOptions {
+    let identifiers = if unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+    LexerOptions { keep_comments, identifiers, trivia }
+}
+
+#[test]
+fn invalid_edits_are_rejected() {
+    let source = "let шва = 1;";
+    assert_eq!(TextEdit::new(4..6, "x").apply(source), Ok("let xва = 1;".to_string()));
+    assert_eq!(TextEdit::new(5..6, "x").apply(source), Err(EditError::NotCharBoundary { offset: 5 }));
+    assert_eq!(TextEdit::new(4..7, "x").apply(source), Err(EditError::NotCharBoundary { offset: 7 }));
+    assert_eq!(TextEdit::new(3..40, "x").apply(source), Err(EditError::InvalidRange { range: 3..40, len: source.len() }));
+    let backwards = Range { start: 6, end: 4 };
+    assert_eq!(TextEdit::new(backwards.clone(), "x").apply(source), Err(EditError::InvalidRange { range: backwards, len: source.len() }));
+    assert_eq!(
+        TextEdit::new(5..6, "x").apply(source).unwrap_err().to_string(),
+        "cannot apply edit: byte offset 5 is not on a character boundary"
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn relex_matches_full_lex(
+        source in fragments(60),
+        edits in prop::collection::vec((any::<Index>(), 0..12usize, fragments(6)), 1..8),
+        keep_comments in any::<bool>(),
+        unicode in any::<bool>(),
+        trivia in any::<bool>(),
+    ) {
+        let options = options(keep_comments, unicode, trivia);
+        let mut source = source;
+        let mut output = lex_with_options(&source, options);
+
+        for (position, length, text) in edits {
+            let start = char_boundary(&source, position.index(source.len() + 1));
+            let end = char_boundary(&source, (start + length).min(source.len()));
+            let edit = TextEdit::new(start..end, &text);
+            source = edit.apply(&source).unwrap();
+
+            output = relex_with_options(&source, output, &edit, options);
+            let expected = lex_with_options(&source, options);
+            prop_assert_eq!(&output.tokens, &expected.tokens, "source {:?}", source);
+            prop_assert_eq!(&output.diagnostics, &expected.diagnostics, "source {:?}", source);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use lab1::ast::Program;
+use lab1::{interpret, interpret_function, lex_with_options, parse, IdentifierMode, IntType, LexerOptions, RuntimeError, Value};
+use proptest::prelude::*;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn program(source: &str) -> Program {
+    let options = LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() };
+    let parsed = parse(&lex_with_options(source, options).tokens);
+    assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+    parsed.program
+}
+
+fn run(source: &str) -> (String, Result<Value, RuntimeError>) {
+    let mut out = Vec::new();
+    let result = interpret(&program(source), &mut out);
+    (String::from_utf8(out).unwrap(), result)
+}
+
+fn error(source: &str) -> (String, (usize, usize)) {
+    let error = run(source).1.unwrap_err();
+    (error.to_string(), (error.span().line, error.span().column))
+}
+
+#[test]
+fn runs_a_teaching_program() {
+    let source = r#"
+fn fact(n: u64) -> u64 {
+    if n <= 1 {
+        return 1;
+    }
+    n * fact(n - 1)
+}
+
+fn main() {
+    let a: f64 = 2.0;
+    let sk = sqrt(a) * 2.5e-3;
+    println!("sk = {:.6}, sin = {:.4}, cos = {:.4}", sk, sin(a), a.cos());
+    println!("tan = {:.4}, ctan = {:.4}", tan(1.0), ctan(1.0f32));
+    let mut total = 0;
+    let mut i = 0;
+    while i < 10 {
+        i += 1;
+        if i % 2 == 0 {
+            continue;
+        }
+        total += i;
+    }
+    let found = 'outer: loop {
+        for j in 1..=10 {
+            if j * j > total {
+                break 'outer j;
+            }
+        }
+        break 0;
+    };
+    let mut s = new();
+    let name = "shvachka";
+    s = s + name;
+    println!("{} {total} {found} {s:?} {} {}", fact(20), !5u8, 1 << 4);
+    print!("{}", 7 / 2 == 3 && -7 % 3 == -1 || false);
+}
+"#;
+    let (output, result) = run(source);
+    assert_eq!(result, Ok(Value::Unit));
+    assert_eq!(
+        output,
+        "sk = 0.003536, sin = 0.9093, cos = -0.4161\ntan = 1.5574, ctan = 0.6421\n2432902008176640000 25 6 \"shvachka\" 250 16\ntrue"
+    );
+}
+
+#[test]
+fn entry_function_returns_a_typed_value() {
+    let result = interpret_function(&program("fn shvachka() -> i16 { let mut a: i16 = 3; a *= -4; a }"), "shvachka", &mut Vec::new());
+    assert_eq!(result, Ok(Value::Int { value: -12, ty: Some(IntType::I16) }));
+    assert_eq!(run("fn a() {}").1.unwrap_err().to_string(), "cannot find `main` in this scope");
+}
+
+#[test]
+fn typed_overflow_is_reported() {
+    assert_eq!(
+        error("fn main() {\n    let mut sk: u8 = 250;\n    while true { sk += 1; }\n}"),
+        ("attempt to add with overflow in `u8`".to_string(), (3, 18))
+    );
+    assert_eq!(error("fn main() { let a: i8 = -128; let b = -a; }").0, "attempt to negate with overflow in `i8`");
+    assert_eq!(error("fn main() { let a = 2147483647 + 1; }").0, "attempt to add with overflow in `i32`");
+    assert_eq!(error("fn main() { let a = 3u32 - 4; }").0, "attempt to subtract with overflow in `u32`");
+    assert_eq!(error("fn main() { let a = 1 << 40; }").0, "attempt to shift left with overflow in `i32`");
+    assert_eq!(error("fn main() { let a: u16 = 1 << 16; }").0, "attempt to shift left with overflow in `u16`");
+    assert_eq!(error("fn main() { let a = 1u16 << 16; }").0, "attempt to shift left with overflow in `u16`");
+    assert_eq!(error("fn main() { let a: u8 = 200; let b = a * 2; }").0, "attempt to multiply with overflow in `u8`");
+    assert_eq!(error("fn main() {\n    let a: u8 = 256;\n}"), ("value 256 is out of range for `u8`".to_string(), (2, 17)));
+    assert_eq!(error("fn main() { let a: u8 = 10; let b = a + 300; }").0, "value 300 is out of range for `u8`");
+    assert_eq!(error("fn main() { let a = 5 / (3 - 3); }").0, "attempt to divide by zero");
+}
+
+#[test]
+fn declared_types_reach_unsuffixed_literals() {
+    let source = "fn f(x: i64) -> i64 { x }
+                  fn g() -> u64 { 5000000000 * 2 }
+                  fn main() {
+                      let s: i128 = 3000000000 - 1;
+                      let mut t: i64 = 0;
+                      t = -(4000000000 + 1);
+                      t += 1 << 40;
+                      println!(\"{} {} {} {}\", s, t, f(3000000000 * 2), g());
+                  }";
+    assert_eq!(run(source), ("2999999999 1095511627775 6000000000 10000000000\n".to_string(), Ok(Value::Unit)));
+    assert_eq!(error("fn main() { let a = 3000000000 - 1; }").0, "value 3000000000 is out of range for `i32`");
+    assert_eq!(error("fn main() { let a = -3000000000; let b = a + 1; }").0, "value -3000000000 is out of range for `i32`");
+    assert_eq!(run("fn main() { let a: i128 = 2147483647; print!(\"{}\", a + 1 < 3000000000); }").0, "true");
+}
+
+#[test]
+fn u128_values_use_the_full_range() {
+    let source = "fn main() {
+                      let a: u128 = 170141183460469231731687303715884105727;
+                      let b = a + 1;
+                      let c: u128 = 340282366920938463463374607431768211455;
+                      println!(\"{} {} {}\", b, c == !0u128, b < c);
+                      println!(\"{} {} {}\", 1u128 << 127, c >> 127, c / 3);
+                      for i in c - 1..=c { print!(\"{} \", i % 10); }
+                  }";
+    assert_eq!(run(source).0, "170141183460469231731687303715884105728 true true\n170141183460469231731687303715884105728 1 113427455640312821154458202477256070485\n4 5 ");
+    assert_eq!(error("fn main() { let c = 340282366920938463463374607431768211455u128; let d = c + 1; }").0, "attempt to add with overflow in `u128`");
+    assert_eq!(error("fn main() { let a = 0u128 - 1; }").0, "attempt to subtract with overflow in `u128`");
+    assert_eq!(error("fn main() { let a: i128 = 170141183460469231731687303715884105728; }").0, "value 170141183460469231731687303715884105728 is out of range for `i128`");
+}
+
+#[test]
+fn type_errors_are_reported() {
+    assert_eq!(error("fn main() { let a: f64 = 2; }").0, "mismatched types: expected `f64`, found `{integer}`");
+    assert_eq!(error("fn main() { let a = 1u8 + 1i32; }").0, "mismatched types: expected `u8`, found `i32`");
+    assert_eq!(error("fn main() { let a = sqrt(4); }").0, "mismatched types: expected `{float}`, found `{integer}`");
+    assert_eq!(error("fn main() { if 1 { } }").0, "mismatched types: expected `bool`, found `{integer}`");
+    assert_eq!(error("fn main() { let a = 3u8; let b = -a; }").0, "cannot apply `-` to a value of type `u8`");
+    assert_eq!(error("fn main() { let a = 1.5 & 2.0; }").0, "cannot apply `&` to a value of type `{float}`");
+    assert_eq!(error("fn main() {\n    let a = 1;\n    a = 2;\n}"), ("cannot assign twice to immutable variable `a`".to_string(), (3, 5)));
+    assert_eq!(error("fn main() { let a: i32; a += 1; }").0, "used binding `a` before it was initialized");
+    assert_eq!(error("fn main() { let a = b; }").0, "cannot find `b` in this scope");
+    assert_eq!(error("fn f(a: i32) -> i32 { a } fn main() { f(1, 2); }").0, "function `f` takes 1 argument(s) but 2 were supplied");
+    assert_eq!(error("fn f() -> u8 { 256 } fn main() { f(); }").0, "value 256 is out of range for `u8`");
+    assert_eq!(error("fn main() { let a = [1, 2]; }").0, "an array is not supported by the interpreter");
+    assert_eq!(error("fn main() { println!(\"{} {}\", 1); }").0, "invalid format string: missing argument 1");
+}
+
+#[test]
+fn deep_recursion_stops_at_the_limit() {
+    let (_, result) = run("fn f(n: u32) -> u32 { f(n + 1) } fn main() { f(0); }");
+    assert_eq!(result.unwrap_err().to_string(), "recursion limit of 1000 nested calls reached");
+    let source = "fn countdown(n: u32) -> u32 { if n == 0 { 0 } else { 1 + countdown(n - 1) } }\n\
+                  fn fact(n: f64) -> f64 { if n <= 1.0 { 1.0 } else { n * fact(n - 1.0) } }\n\
+                  fn main() { print!(\"{} {}\", countdown(998), fact(70.0) > 1e100); }";
+    let (output, result) = run(source);
+    assert_eq!((output.as_str(), result), ("998 true", Ok(Value::Unit)));
+}
+
+fn run_cli(source: &str) -> (String, String, Option<i32>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lab1"))
+        .args(["--format", "run"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(source.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap(), output.status.code())
+}
+
+#[test]
+fn run_mode_exit_code_ignores_the_identifier_alphabet() {
+    let (stdout, stderr, code) = run_cli("fn main() { let x = 2; println!(\"{}\", x + 1); }");
+    assert_eq!((stdout.as_str(), code), ("3\n", Some(0)));
+    assert!(stderr.contains("illegal character `m` in identifier"), "{}", stderr);
+    assert_eq!(run_cli("fn main() { let a: u8 = 256; }").2, Some(1));
+    assert_eq!(run_cli("fn main() { let a = \"; }").2, Some(1));
+}
+
+fn checked(op: &str, a: i128, b: i128, ty: IntType) -> Option<i128> {
+    let result = match op {
+        "+" => a.checked_add(b),
+        "-" => a.checked_sub(b),
+        "*" => a.checked_mul(b),
+        "/" => a.checked_div(b),
+        _ => a.checked_rem(b),
+    };
+    result.filter(|value| ty.contains(*value))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn integer_arithmetic_matches_checked_operations(
+        a in -40_000i128..70_000,
+        b in -40_000i128..70_000,
+        op in prop::sample::select(vec!["+", "-", "*", "/", "%"]),
+        ty in prop::sample::select(vec![IntType::U8, IntType::I8, IntType::U16, IntType::I16, IntType::I32]),
+    ) {
+        prop_assume!(ty.contains(a) && ty.contains(b));
+        let source = format!("fn main() {{ let a: {0} = {1}; let b: {0} = {2}; println!(\"{{}}\", a {3} b); }}", ty.name(), a, b, op);
+        let (output, result) = run(&source);
+        match checked(op, a, b, ty) {
+            Some(value) => {
+                prop_assert_eq!(result, Ok(Value::Unit));
+                prop_assert_eq!(output, format!("{}\n", value));
+            }
+            None => prop_assert!(result.is_err()),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use lab1::{lex_with_options, IdentifierMode, LexerOptions, TokenType};
+
+fn tokens(source: &str) -> Vec<(TokenType, String)> {
+    let options = LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() };
+    let output = lex_with_options(source, options);
+    assert!(output.diagnostics.is_empty(), "{}: {:?}", source, output.diagnostics);
+    output.tokens.into_iter().map(|t| (t.type_name, t.content)).collect()
+}
+
+fn kinds(source: &str) -> Vec<TokenType> {
+    tokens(source).into_iter().map(|(kind, _)| kind).collect()
+}
+
+#[test]
+fn lifetimes_are_single_tokens() {
+    assert_eq!(
+        tokens("&'a T"),
+        [(TokenType::Delimiter, "&".to_string()), (TokenType::Lifetime, "'a".to_string()), (TokenType::Identifier, "T".to_string())]
+    );
+    assert_eq!(tokens("'static")[0], (TokenType::Lifetime, "'static".to_string()));
+    assert_eq!(tokens("'_")[0], (TokenType::Lifetime, "'_".to_string()));
+    assert_eq!(tokens("'життя")[0], (TokenType::Lifetime, "'життя".to_string()));
+}
+
+#[test]
+fn char_literals_win_over_lifetimes() {
+    assert_eq!(kinds("'a'"), [TokenType::CharLiteral]);
+    assert_eq!(kinds("'a 'b'"), [TokenType::Lifetime, TokenType::CharLiteral]);
+    assert_eq!(kinds("f('a', 'b)"), [TokenType::Identifier, TokenType::Delimiter, TokenType::CharLiteral, TokenType::Delimiter, TokenType::Lifetime, TokenType::Delimiter]);
+}
+
+#[test]
+fn lone_quote_stays_a_delimiter() {
+    assert_eq!(tokens("' x")[0], (TokenType::Delimiter, "'".to_string()));
+    assert_eq!(tokens("'1")[0], (TokenType::Delimiter, "'".to_string()));
+}
+
+#[test]
+fn loop_labels_are_lifetimes_followed_by_a_colon() {
+    assert_eq!(
+        kinds("'outer: loop { break 'outer; }"),
+        [
+            TokenType::Lifetime,
+            TokenType::Delimiter,
+            TokenType::KeyWord,
+            TokenType::Delimiter,
+            TokenType::KeyWord,
+            TokenType::Lifetime,
+            TokenType::Delimiter,
+            TokenType::Delimiter,
+        ]
+    );
+}
+
+#[test]
+fn attributes_start_with_hash_or_hash_bang() {
+    assert_eq!(tokens("#[derive(Debug)]")[0], (TokenType::Attribute, "#".to_string()));
+    assert_eq!(tokens("#![allow(dead_code)]")[0], (TokenType::Attribute, "#!".to_string()));
+    assert_eq!(kinds("#[test]"), [TokenType::Attribute, TokenType::Delimiter, TokenType::Identifier, TokenType::Delimiter]);
+}
+
+#[test]
+fn macro_invocations_include_the_bang() {
+    assert_eq!(tokens("println!(\"{}\", x)")[0], (TokenType::Macro, "println!".to_string()));
+    assert_eq!(tokens("vec![1]")[0], (TokenType::Macro, "vec!".to_string()));
+    assert_eq!(tokens("друк!{}")[0], (TokenType::Macro, "друк!".to_string()));
+}
+
+#[test]
+fn bang_equals_is_not_a_macro() {
+    assert_eq!(
+        tokens("a!=b"),
+        [(TokenType::Identifier, "a".to_string()), (TokenType::Comparison, "!=".to_string()), (TokenType::Identifier, "b".to_string())]
+    );
+    assert_eq!(kinds("if !x"), [TokenType::KeyWord, TokenType::Logical, TokenType::Identifier]);
+    assert_eq!(kinds("fn!"), [TokenType::KeyWord, TokenType::Logical]);
+}
+
+#[test]
+fn macro_keyword_is_a_keyword() {
+    assert_eq!(tokens("macro square")[0], (TokenType::KeyWord, "macro".to_string()));
+}
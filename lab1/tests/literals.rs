@@ -0,0 +1,83 @@
+use lab1::{lex, LexError, LiteralValue, TokenType};
+
+fn single(source: &str) -> (TokenType, LiteralValue) {
+    let output = lex(source);
+    assert!(output.diagnostics.is_empty(), "{:?}", output.diagnostics);
+    assert_eq!(output.tokens.len(), 1, "{:?}", output.tokens);
+    let token = output.tokens.into_iter().next().unwrap();
+    assert_eq!(token.content, source);
+    (token.type_name, token.value.expect("literal value"))
+}
+
+fn error(source: &str) -> LexError {
+    let output = lex(source);
+    assert_eq!(output.diagnostics.len(), 1, "{:?}", output.diagnostics);
+    output.diagnostics.into_iter().next().unwrap()
+}
+
+#[test]
+fn string_escapes_are_decoded() {
+    let (token_type, value) = single(r#""a\n\r\t\\\0\'\"b""#);
+    assert_eq!(token_type, TokenType::StringLiteral);
+    assert_eq!(value, LiteralValue::Str("a\n\r\t\\\0'\"b".to_string()));
+    assert_eq!(single(r#""\x41\u{1F600}\u{4_1}""#).1, LiteralValue::Str("A😀A".to_string()));
+    assert_eq!(single("\"швачка\"").1, LiteralValue::Str("швачка".to_string()));
+}
+
+#[test]
+fn escaped_newline_skips_leading_whitespace() {
+    assert_eq!(single("\"one \\\n      two\"").1, LiteralValue::Str("one two".to_string()));
+    assert_eq!(single("\"one\\\r\n\ttwo\"").1, LiteralValue::Str("onetwo".to_string()));
+}
+
+#[test]
+fn raw_strings_keep_their_body() {
+    assert_eq!(single(r#"r"a\nb""#).1, LiteralValue::Str(r"a\nb".to_string()));
+    assert_eq!(single(r###"r##"say "#hi"#"##"###).1, LiteralValue::Str(r##"say "#hi"#"##.to_string()));
+    assert_eq!(single(r#"br"\x00""#).1, LiteralValue::ByteStr(br"\x00".to_vec()));
+}
+
+#[test]
+fn byte_strings_and_bytes_are_decoded() {
+    assert_eq!(single(r#"b"a\xFF\n""#), (TokenType::StringLiteral, LiteralValue::ByteStr(vec![b'a', 0xFF, b'\n'])));
+    assert_eq!(single(r"b'\x7f'"), (TokenType::CharLiteral, LiteralValue::Byte(0x7F)));
+    assert_eq!(single("b'z'").1, LiteralValue::Byte(b'z'));
+}
+
+#[test]
+fn char_literals_are_decoded() {
+    assert_eq!(single("'a'"), (TokenType::CharLiteral, LiteralValue::Char('a')));
+    assert_eq!(single(r"'\''").1, LiteralValue::Char('\''));
+    assert_eq!(single(r"'\u{44F}'").1, LiteralValue::Char('я'));
+    assert_eq!(single("'ш'").1, LiteralValue::Char('ш'));
+}
+
+#[test]
+fn invalid_escapes_are_reported() {
+    let cases = [
+        (r#""\q""#, "invalid string literal: unknown character escape `\\q`"),
+        (r#""\x4""#, "invalid string literal: numeric character escape is too short"),
+        (r#""\x80""#, "invalid string literal: out of range hex escape `\\x80`"),
+        (r#""\u41""#, "invalid string literal: incorrect unicode escape sequence"),
+        (r#""\u{41""#, "invalid string literal: unterminated unicode escape"),
+        (r#""\u{}""#, "invalid string literal: unicode escape must have between 1 and 6 hex digits"),
+        (r#""\u{D800}""#, "invalid string literal: invalid unicode character escape `\\u{D800}`"),
+        (r#"b"\u{41}""#, "invalid string literal: unicode escape in byte literal"),
+        ("b\"ш\"", "invalid string literal: non-ASCII character `ш` in byte string"),
+        ("br\"ш\"", "invalid string literal: non-ASCII character in raw byte string"),
+        (r"'\q'", "invalid character literal: unknown character escape `\\q`"),
+        ("b'ш'", "invalid character literal: non-ASCII character `ш` in byte literal"),
+    ];
+    for (source, message) in cases {
+        let error = error(source);
+        assert_eq!(error.to_string(), message, "{}", source);
+        assert_eq!((error.span().start, error.span().end), (0, source.len()), "{}", source);
+    }
+}
+
+#[test]
+fn unterminated_strings_are_reported() {
+    assert_eq!(error("\"abc").to_string(), "unterminated string literal");
+    assert_eq!(error("r#\"abc\"").to_string(), "unterminated string literal");
+    assert_eq!(error("\"abc\\").to_string(), "unterminated string literal");
+}
@@ -0,0 +1,74 @@
+use lab1::{format_source, lex_with_options, IdentifierMode, LexerOptions, Token, TokenType};
+use proptest::prelude::*;
+
+const FRAGMENTS: [&str; 40] = [
+    "fn", "let", "mut", "if", "else", "shvachka", "vasya", "x", "1", "25", "2.5", "-3", "0x1f", "+", "-", "*", "/", "=",
+    "==", "+=", "!", "&&", "<", ">", "..", ".", "::", "{", "}", "(", ")", "[", "]", ";", ",", ":", "sqrt", "i32", " ",
+    "\n",
+];
+
+const SOURCE: &str = "fn shvachka(a:f64)->f64{\r\n    let mut sk=sqrt(a)*2.5e-3 -12;\t// коментар\n\n\n  sk+=1;/* block */ return sk}\n";
+
+fn fragments(max: usize) -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(&FRAGMENTS[..]), 0..max).prop_map(|parts| parts.concat())
+}
+
+fn trivia(unicode: bool) -> LexerOptions {
+    let identifiers = if unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+    LexerOptions { identifiers, trivia: true, ..LexerOptions::default() }
+}
+
+fn concat(tokens: &[Token]) -> String {
+    tokens.iter().map(|t| t.content.as_str()).collect()
+}
+
+fn significant(source: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    for token in lex_with_options(source, LexerOptions::default()).tokens {
+        if matches!(token.type_name, TokenType::Integer | TokenType::Double) && token.content.starts_with(['-', '+']) {
+            parts.push(token.content[..1].to_string());
+            parts.push(token.content[1..].to_string());
+        } else {
+            parts.push(token.content);
+        }
+    }
+    parts
+}
+
+#[test]
+fn trivia_tokens_rebuild_the_source() {
+    for unicode in [false, true] {
+        let output = lex_with_options(SOURCE, trivia(unicode));
+        assert_eq!(concat(&output.tokens), SOURCE);
+        assert!(output.tokens.iter().any(|t| t.type_name == TokenType::Newline && t.content == "\r\n"));
+        assert!(output.tokens.iter().any(|t| t.type_name == TokenType::Whitespace && t.content == "\t"));
+        assert!(output.tokens.iter().any(|t| t.type_name == TokenType::Comment));
+    }
+}
+
+#[test]
+fn formatter_normalises_spacing() {
+    let formatted = format_source(SOURCE);
+    assert_eq!(
+        formatted,
+        "fn shvachka(a: f64) -> f64 {\n    let mut sk = sqrt(a) * 2.5e-3 - 12; // коментар\n\n    sk += 1; /* block */ return sk }\n"
+    );
+    assert_eq!(format_source(&formatted), formatted);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn trivia_round_trips_arbitrary_text(source in "(\\PC|\\s|\r\n){0,80}", unicode in any::<bool>()) {
+        let output = lex_with_options(&source, trivia(unicode));
+        prop_assert_eq!(concat(&output.tokens), source);
+    }
+
+    #[test]
+    fn formatting_is_idempotent_and_keeps_tokens(source in fragments(60)) {
+        let formatted = format_source(&source);
+        prop_assert_eq!(&format_source(&formatted), &formatted, "source {:?}", source);
+        prop_assert_eq!(significant(&formatted), significant(&source), "source {:?}", source);
+    }
+}
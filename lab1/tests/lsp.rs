@@ -0,0 +1,121 @@
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{ChildStdin, ChildStdout, Command, Stdio};
+
+struct Client {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Client {
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn request(&mut self, id: i64, method: &str, params: Value) -> Value {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn receive_notification(&mut self, method: &str) -> Value {
+        loop {
+            let message = self.receive();
+            if message["method"] == method {
+                return message;
+            }
+        }
+    }
+}
+
+#[test]
+fn scripted_session() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lab1-lsp"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut client = Client {
+        stdin: child.stdin.take().unwrap(),
+        stdout: BufReader::new(child.stdout.take().unwrap()),
+    };
+
+    let initialized = client.request(1, "initialize", json!({ "capabilities": {} }));
+    let capabilities = &initialized["result"]["capabilities"];
+    assert_eq!(capabilities["documentSymbolProvider"], true);
+    assert!(capabilities["semanticTokensProvider"]["legend"]["tokenTypes"].as_array().unwrap().contains(&json!("keyword")));
+    client.notify("initialized", json!({}));
+
+    let uri = "file:///tmp/session.lab1";
+    let text = "fn shvachka() {\n    let mut vasya: i32 = 5;\n    vasya += 1;\n}\n$\n\"open;\n";
+    client.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": "lab1", "version": 1, "text": text }
+    }));
+    let published = client.receive_notification("textDocument/publishDiagnostics");
+    let diagnostics = published["params"]["diagnostics"].as_array().unwrap();
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d["message"].as_str().unwrap()).collect();
+    assert!(messages.contains(&"illegal character `y` in identifier"), "{:?}", messages);
+    assert!(messages.contains(&"unexpected character `$`"), "{:?}", messages);
+    assert!(messages.contains(&"unterminated string literal"), "{:?}", messages);
+    let dollar = diagnostics.iter().find(|d| d["message"] == "unexpected character `$`").unwrap();
+    assert_eq!(dollar["range"]["start"], json!({ "line": 4, "character": 0 }));
+
+    let tokens = client.request(2, "textDocument/semanticTokens/full", json!({ "textDocument": { "uri": uri } }));
+    let data = tokens["result"]["data"].as_array().unwrap();
+    assert_eq!(data.len() % 5, 0);
+    assert_eq!(&data[..5], &[json!(0), json!(0), json!(2), json!(0), json!(0)]);
+
+    let symbols = client.request(3, "textDocument/documentSymbol", json!({ "textDocument": { "uri": uri } }));
+    let names: Vec<&str> = symbols["result"].as_array().unwrap().iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["shvachka", "vasya"]);
+
+    let invalid = client.request(4, "textDocument/documentSymbol", json!({ "textDocument": { "url": uri } }));
+    assert_eq!(invalid["error"]["code"], -32602);
+    assert!(invalid["error"]["message"].as_str().unwrap().starts_with("invalid params for `textDocument/documentSymbol`"), "{}", invalid);
+    client.notify("textDocument/didOpen", json!({ "textDocument": { "uri": 5 } }));
+    client.notify("textDocument/didChange", json!(["not", "an", "object"]));
+
+    client.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": "fn shvachka() {}\n" }]
+    }));
+    let published = client.receive_notification("textDocument/publishDiagnostics");
+    assert_eq!(published["params"]["diagnostics"], json!([]));
+
+    let shutdown = client.request(5, "shutdown", Value::Null);
+    assert_eq!(shutdown["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let log = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(log.matches("ignoring").count(), 2, "{}", log);
+}
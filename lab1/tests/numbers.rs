@@ -0,0 +1,97 @@
+use lab1::{lex, LiteralValue, TokenType};
+
+fn number(source: &str) -> (TokenType, LiteralValue) {
+    let output = lex(source);
+    assert!(output.diagnostics.is_empty(), "{}: {:?}", source, output.diagnostics);
+    assert_eq!(output.tokens.len(), 1, "{}: {:?}", source, output.tokens);
+    let token = output.tokens.into_iter().next().unwrap();
+    assert_eq!(token.content, source);
+    (token.type_name, token.value.expect("literal value"))
+}
+
+fn integer(value: i128, suffix: Option<&str>) -> (TokenType, LiteralValue) {
+    (TokenType::Integer, LiteralValue::Integer { value: value.unsigned_abs(), negative: value < 0, suffix: suffix.map(str::to_string) })
+}
+
+fn float(value: f64, suffix: Option<&str>) -> (TokenType, LiteralValue) {
+    (TokenType::Double, LiteralValue::Float { value, suffix: suffix.map(str::to_string) })
+}
+
+fn error(source: &str) -> String {
+    let output = lex(source);
+    assert_eq!(output.diagnostics.len(), 1, "{}: {:?}", source, output.diagnostics);
+    let error = &output.diagnostics[0];
+    assert_eq!((error.span().start, error.span().end), (0, source.len()), "{}", source);
+    error.to_string()
+}
+
+#[test]
+fn decimal_integers_and_underscores() {
+    assert_eq!(number("0"), integer(0, None));
+    assert_eq!(number("1_000_000"), integer(1_000_000, None));
+    assert_eq!(number("-42"), integer(-42, None));
+    assert_eq!(number("+7"), integer(7, None));
+}
+
+#[test]
+fn prefixed_integers() {
+    assert_eq!(number("0xff"), integer(255, None));
+    assert_eq!(number("0xDEAD_beef"), integer(0xDEAD_BEEF, None));
+    assert_eq!(number("0o755"), integer(0o755, None));
+    assert_eq!(number("0b1010_1010"), integer(0b1010_1010, None));
+    assert_eq!(number("-0x10"), integer(-16, None));
+}
+
+#[test]
+fn floats_and_exponents() {
+    assert_eq!(number("2.5"), float(2.5, None));
+    assert_eq!(number("1."), float(1.0, None));
+    assert_eq!(number("1e3"), float(1000.0, None));
+    assert_eq!(number("2.5E-3"), float(0.0025, None));
+    assert_eq!(number("6.02e+23"), float(6.02e23, None));
+    assert_eq!(number("-0.5"), float(-0.5, None));
+}
+
+#[test]
+fn suffixed_literals() {
+    assert_eq!(number("7u8"), integer(7, Some("u8")));
+    assert_eq!(number("255u8"), integer(255, Some("u8")));
+    assert_eq!(number("-128i8"), integer(-128, Some("i8")));
+    assert_eq!(number("0xffu16"), integer(255, Some("u16")));
+    assert_eq!(number("1_i64"), integer(1, Some("i64")));
+    assert_eq!(number("-170141183460469231731687303715884105728i128"), integer(i128::MIN, Some("i128")));
+    assert_eq!(number("340282366920938463463374607431768211455u128").1, LiteralValue::Integer { value: u128::MAX, negative: false, suffix: Some("u128".to_string()) });
+    assert_eq!(number("3f32"), float(3.0, Some("f32")));
+    assert_eq!(number("1.5f64"), float(1.5, Some("f64")));
+}
+
+#[test]
+fn ranges_and_methods_are_not_floats() {
+    let types: Vec<TokenType> = lex("1..2").tokens.into_iter().map(|t| t.type_name).collect();
+    assert_eq!(types, [TokenType::Integer, TokenType::Delimiter, TokenType::Integer]);
+    let contents: Vec<String> = lex("1.max(2)").tokens.into_iter().map(|t| t.content).collect();
+    assert_eq!(contents[..3], ["1", ".", "max"]);
+    let contents: Vec<String> = lex("1-1").tokens.into_iter().map(|t| t.content).collect();
+    assert_eq!(contents, ["1", "-", "1"]);
+}
+
+#[test]
+fn malformed_numbers_are_reported() {
+    let cases = [
+        ("0x", "malformed number: missing digits after the integer base prefix"),
+        ("0b102", "malformed number: invalid digit `2` in base 2 literal"),
+        ("0o8", "malformed number: invalid digit `8` in base 8 literal"),
+        ("1.2.3", "malformed number: number has more than one decimal point"),
+        ("256u8", "malformed number: literal out of range for `u8`"),
+        ("-129i8", "malformed number: literal out of range for `i8`"),
+        ("-1u32", "malformed number: literal out of range for `u32`"),
+        ("1.5u8", "malformed number: invalid suffix `u8` for float literal"),
+        ("0xfg", "malformed number: invalid suffix `g` for number literal"),
+        ("0b1f32", "malformed number: base 2 float literals are not supported"),
+        ("12abc", "malformed number: invalid suffix `abc` for number literal"),
+        ("340282366920938463463374607431768211456", "malformed number: integer literal is too large"),
+    ];
+    for (source, message) in cases {
+        assert_eq!(error(source), message, "{}", source);
+    }
+}
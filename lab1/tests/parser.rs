@@ -0,0 +1,209 @@
+use lab1::ast::{BinaryOp, ExprKind, Fields, ItemKind, Program, StmtKind, TypeKind, UnaryOp};
+use lab1::{lex_with_options, parse, IdentifierMode, LexerOptions, ParseError, ParseOutput};
+
+fn parse_source(source: &str) -> ParseOutput {
+    let options = LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() };
+    parse(&lex_with_options(source, options).tokens)
+}
+
+fn program(source: &str) -> Program {
+    let parsed = parse_source(source);
+    assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+    parsed.program
+}
+
+fn errors(source: &str) -> Vec<(String, (usize, usize, usize))> {
+    parse_source(source)
+        .diagnostics
+        .iter()
+        .map(|e| (e.to_string(), (e.span().line, e.span().column, e.span().end - e.span().start)))
+        .collect()
+}
+
+fn names(program: &Program) -> Vec<String> {
+    program
+        .items
+        .iter()
+        .map(|item| match &item.kind {
+            ItemKind::Function(function) => format!("fn {}", function.name.name),
+            ItemKind::Struct(item) => format!("struct {}", item.name.name),
+            ItemKind::Enum(item) => format!("enum {}", item.name.name),
+            ItemKind::Impl(item) => format!("impl {}", item.self_type),
+            ItemKind::Use(item) => format!("use {}{}", item.path, if item.glob { "::*" } else { "" }),
+            ItemKind::Macro(item) => format!("macro {}", item.name.name),
+        })
+        .collect()
+}
+
+fn main_tail(source: &str) -> ExprKind {
+    let program = program(&format!("fn main() {{ {} }}", source));
+    match &program.items[0].kind {
+        ItemKind::Function(function) => function.body.tail.as_ref().expect("tail expression").kind.clone(),
+        kind => panic!("{:?}", kind),
+    }
+}
+
+#[test]
+fn parses_every_item_kind() {
+    let program = program(
+        "#![allow(dead_code)]\n\
+         use std::collections::*;\n\
+         #[derive(Debug)]\n\
+         struct Point<T> { x: T, y: T }\n\
+         struct Pair(i32, f64);\n\
+         enum Shape { Dot, Circle(f64), Rect { w: f64, h: f64 } }\n\
+         impl Point<f64> { fn new(x: f64, y: f64) -> Self { Point { x, y } } fn len(&self) -> f64 { self.x } }\n\
+         macro square { ($x:expr) => { $x * $x } }\n\
+         fn shvachka<'a, T>(a: &'a mut [i32; 3], b: (u8, Vec<Vec<T>>)) {}\n",
+    );
+    assert_eq!(program.attributes.len(), 1);
+    assert_eq!(
+        names(&program),
+        ["use std::collections::*", "struct Point", "struct Pair", "enum Shape", "impl Point<f64>", "macro square", "fn shvachka"]
+    );
+    assert_eq!(program.items[1].attributes[0].path.to_string(), "derive");
+    match &program.items[3].kind {
+        ItemKind::Enum(shape) => {
+            let fields: Vec<&Fields> = shape.variants.iter().map(|v| &v.fields).collect();
+            assert!(matches!(fields[..], [Fields::Unit, Fields::Tuple(_), Fields::Named(_)]));
+        }
+        kind => panic!("{:?}", kind),
+    }
+    match &program.items[4].kind {
+        ItemKind::Impl(item) => assert_eq!(item.functions.len(), 2),
+        kind => panic!("{:?}", kind),
+    }
+    match &program.items[6].kind {
+        ItemKind::Function(function) => {
+            let generics: Vec<&str> = function.generics.iter().map(|g| g.name.as_str()).collect();
+            assert_eq!(generics, ["'a", "T"]);
+            let types: Vec<String> = function.params.iter().map(|p| p.ty.as_ref().unwrap().to_string()).collect();
+            assert_eq!(types, ["&'a mut [i32; 3]", "(u8, Vec<Vec<T>>)"]);
+        }
+        kind => panic!("{:?}", kind),
+    }
+}
+
+#[test]
+fn parses_statements() {
+    let program = program("fn main() { let mut x: i32 = 1; let _ = x; fn inner() {} x += 2; ; x }");
+    let ItemKind::Function(main) = &program.items[0].kind else { panic!() };
+    let kinds: Vec<&str> = main
+        .body
+        .statements
+        .iter()
+        .map(|s| match &s.kind {
+            StmtKind::Let { .. } => "let",
+            StmtKind::Item(_) => "item",
+            StmtKind::Expr(_) => "expr",
+            StmtKind::Semi(_) => "semi",
+        })
+        .collect();
+    assert_eq!(kinds, ["let", "let", "item", "semi"]);
+    assert!(matches!(main.body.tail.as_deref().map(|e| &e.kind), Some(ExprKind::Path(_))));
+    let StmtKind::Let { mutable, name, ty, .. } = &main.body.statements[0].kind else { panic!() };
+    assert!(*mutable);
+    assert_eq!(name.name, "x");
+    assert!(matches!(ty.as_ref().map(|t| &t.kind), Some(TypeKind::Path { .. })));
+}
+
+#[test]
+fn binary_operators_follow_precedence() {
+    let ExprKind::Binary { op, lhs, rhs } = main_tail("1 + 2 * 3 - -x") else { panic!() };
+    assert_eq!(op, BinaryOp::Sub);
+    assert!(matches!(rhs.kind, ExprKind::Unary { op: UnaryOp::Neg, .. }));
+    let ExprKind::Binary { op, rhs, .. } = lhs.kind else { panic!() };
+    assert_eq!(op, BinaryOp::Add);
+    assert!(matches!(rhs.kind, ExprKind::Binary { op: BinaryOp::Mul, .. }));
+
+    let ExprKind::Binary { op, lhs, .. } = main_tail("a || b && c == d") else { panic!() };
+    assert_eq!(op, BinaryOp::Or);
+    assert!(matches!(lhs.kind, ExprKind::Path(_)));
+}
+
+#[test]
+fn parses_postfix_and_primary_expressions() {
+    assert!(matches!(main_tail("v.iter().len()"), ExprKind::MethodCall { .. }));
+    assert!(matches!(main_tail("t.0.1"), ExprKind::Field { .. }));
+    assert!(matches!(main_tail("a[i]?"), ExprKind::Try(_)));
+    assert!(matches!(main_tail("sqrt(2.0)"), ExprKind::BuiltinCall { .. }));
+    assert!(matches!(main_tail("Point { x: 1, y }"), ExprKind::StructLiteral { .. }));
+    assert!(matches!(main_tail("(1, [2, 3])"), ExprKind::Tuple(_)));
+    assert!(matches!(main_tail("println!(\"{}\", 1)"), ExprKind::MacroCall { .. }));
+    assert!(matches!(main_tail("..=10"), ExprKind::Range { start: None, inclusive: true, .. }));
+    assert!(matches!(main_tail("&mut x"), ExprKind::Unary { op: UnaryOp::RefMut, .. }));
+}
+
+#[test]
+fn parses_control_flow() {
+    let ExprKind::If { else_branch, .. } = main_tail("if a { 1 } else if b { 2 } else { 3 }") else { panic!() };
+    assert!(matches!(else_branch.as_deref().map(|e| &e.kind), Some(ExprKind::If { .. })));
+    assert!(matches!(main_tail("'outer: for i in 0..n { break 'outer; }"), ExprKind::For { label: Some(_), .. }));
+    assert!(matches!(main_tail("while x < 10 { x += 1; }"), ExprKind::While { .. }));
+    assert!(matches!(main_tail("loop { continue; }"), ExprKind::Loop { label: None, .. }));
+}
+
+#[test]
+fn struct_literals_are_not_parsed_in_conditions() {
+    let ExprKind::If { condition, .. } = main_tail("if x == y { 1 } else { 2 }") else { panic!() };
+    assert!(matches!(condition.kind, ExprKind::Binary { op: BinaryOp::Eq, .. }));
+}
+
+#[test]
+fn recovery_resumes_at_the_next_statement() {
+    let parsed = parse_source("fn main() {\n    let = 1;\n    let y = 2;\n    y +;\n    let z = y;\n}\nfn other() {}\n");
+    let messages: Vec<String> = parsed.diagnostics.iter().map(ParseError::to_string).collect();
+    assert_eq!(messages, ["expected variable name, found `=`", "expected expression, found `;`"]);
+    assert_eq!(names(&parsed.program), ["fn main", "fn other"]);
+    let ItemKind::Function(main) = &parsed.program.items[0].kind else { panic!() };
+    assert_eq!(main.body.statements.len(), 2);
+}
+
+#[test]
+fn recovery_skips_stray_top_level_tokens() {
+    let parsed = parse_source("1 + 2; let x = 3;\nfn main() {}\n} } fn after() {}");
+    assert_eq!(names(&parsed.program), ["fn main", "fn after"]);
+    let messages: Vec<String> = parsed.diagnostics.iter().map(ParseError::to_string).collect();
+    assert_eq!(messages, ["expected item, found `1`", "expected item, found `}`"]);
+}
+
+#[test]
+fn recovery_always_makes_progress() {
+    for source in ["fn", "fn (", "fn main( {", "struct {", "impl {{{", "fn main() { if { } }", "#[", "macro m {", "fn f() { let x = (1, ; }"] {
+        let parsed = parse_source(source);
+        assert!(!parsed.diagnostics.is_empty(), "{}", source);
+    }
+}
+
+#[test]
+fn diagnostics_point_at_the_offending_token() {
+    assert_eq!(errors("fn main() {\n    let x = 1\n    x\n}"), [("expected `;`, found `x`".to_string(), (3, 5, 1))]);
+    assert_eq!(errors("fn main() {\n    a < b < c;\n}"), [("comparison operators cannot be chained".to_string(), (2, 5, 9))]);
+    assert_eq!(errors("fn main() {\n    1 + 2 = 3;\n}"), [("invalid left-hand side of assignment".to_string(), (2, 5, 5))]);
+    assert_eq!(
+        errors("fn main() {\n    foo(1,"),
+        [("expected expression, found end of input".to_string(), (2, 11, 0)), ("expected `}`, found end of input".to_string(), (2, 11, 0))]
+    );
+    assert_eq!(errors("fn main() -> { }"), [("expected type, found `{`".to_string(), (1, 14, 1))]);
+}
+
+#[test]
+fn deep_nesting_is_reported_instead_of_overflowing() {
+    let depth = 10_000;
+    let sources = [
+        format!("fn main() {{ let x = {}1{}; }}", "(".repeat(depth), ")".repeat(depth)),
+        format!("fn main() {} {}", "{".repeat(depth), "}".repeat(depth)),
+        format!("fn main() {{ let x = {}1; }}", "- ".repeat(depth)),
+        format!("fn main(x: {}i32) {{}}", "& ".repeat(depth)),
+        format!("fn main() {{ if a {{}} {} {{}} }}", "else if a {}".repeat(depth)),
+        format!("fn main() {{ {} }}", "fn f() {".repeat(depth)),
+    ];
+    for source in sources {
+        let parsed = parse_source(&source);
+        let messages: Vec<String> = parsed.diagnostics.iter().map(ParseError::to_string).collect();
+        assert_eq!(messages.first().map(String::as_str), Some("nesting is too deep"), "{:?}", messages);
+    }
+    program(&format!("fn main() {{ let x = {}1{}; }}", "(".repeat(64), ")".repeat(64)));
+    program(&format!("fn main() {} {}", "{".repeat(64), "}".repeat(64)));
+    program(&format!("fn main() {{ let x = {}1; }}", "- ".repeat(64)));
+}
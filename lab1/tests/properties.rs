@@ -0,0 +1,120 @@
+use lab1::{lex, lex_with_options, parse, LexOutput, LexerOptions, TokenType};
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const BINARY_OPERATORS: [&str; 12] = ["+", "-", "*", "/", "%", "<", "<=", "==", "!=", ">=", "&&", "||"];
+const ASSIGNMENT_OPERATORS: [&str; 4] = ["=", "+=", "-=", "*="];
+
+fn identifier() -> impl Strategy<Value = String> {
+    "[shvachkaSHVACHKAшвачкаШВАЧКА][shvachka0-9_швачка]{0,5}"
+}
+
+fn literal() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[0-9]{1,6}",
+        "[0-9]{1,3}\\.[0-9]{1,3}",
+        "0x[0-9a-f]{1,4}",
+        "[0-9]{1,2}(u8|i32|f64)",
+        "\"[a-z шва]{0,8}\"",
+        "'[a-z]'",
+        Just("true".to_string()),
+        Just("false".to_string()),
+    ]
+}
+
+fn expression() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![identifier(), literal()];
+    leaf.prop_recursive(4, 24, 3, |inner| {
+        prop_oneof![
+            (inner.clone(), prop::sample::select(&BINARY_OPERATORS[..]), inner.clone()).prop_map(|(l, op, r)| format!("({} {} {})", l, op, r)),
+            inner.clone().prop_map(|e| format!("!{}", e)),
+            (prop::sample::select(&["sqrt", "sin", "cos", "tan", "ctan"][..]), inner.clone()).prop_map(|(f, e)| format!("{}({})", f, e)),
+            (identifier(), prop::collection::vec(inner, 0..3)).prop_map(|(f, args)| format!("{}({})", f, args.join(", "))),
+        ]
+    })
+}
+
+fn statement() -> impl Strategy<Value = String> {
+    let simple = prop_oneof![
+        (any::<bool>(), identifier(), expression()).prop_map(|(m, name, e)| format!("let {}{} = {};", if m { "mut " } else { "" }, name, e)),
+        (identifier(), prop::sample::select(&ASSIGNMENT_OPERATORS[..]), expression()).prop_map(|(name, op, e)| format!("{} {} {};", name, op, e)),
+        "// [a-z коментар]{0,12}\n",
+        "/\\* [a-z]{0,8} \\*/",
+    ];
+    simple.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            (expression(), prop::collection::vec(inner.clone(), 0..4), prop::collection::vec(inner.clone(), 0..3))
+                .prop_map(|(c, then, other)| format!("if {} {{ {} }} else {{ {} }}", c, then.join(" "), other.join(" "))),
+            (expression(), prop::collection::vec(inner, 0..4)).prop_map(|(c, body)| format!("while {} {{ {} }}", c, body.join(" "))),
+        ]
+    })
+}
+
+fn program() -> impl Strategy<Value = String> {
+    let function = (identifier(), identifier(), prop::collection::vec(statement(), 0..6))
+        .prop_map(|(name, param, body)| format!("fn {}({}: i32) -> f64 {{\n{}\n}}\n", name, param, body.join("\n")));
+    (prop::collection::vec(function, 1..4), prop::sample::select(vec!["\n", "\r\n", "\n\t"]))
+        .prop_map(|(functions, newline)| functions.concat().replace('\n', newline))
+}
+
+fn assert_identifiers(output: &LexOutput) -> Result<(), TestCaseError> {
+    let mut first_seen = HashMap::new();
+    for token in output.tokens.iter().filter(|t| t.type_name == TokenType::Identifier) {
+        first_seen.entry(token.content.as_str()).or_insert(token.span.start);
+    }
+    let identifiers = output.identifiers();
+    let unique: HashSet<&str> = identifiers.iter().copied().collect();
+    prop_assert_eq!(unique.len(), identifiers.len(), "duplicates in {:?}", identifiers);
+    prop_assert_eq!(unique, first_seen.keys().copied().collect::<HashSet<_>>());
+    let starts: Vec<usize> = identifiers.iter().map(|name| first_seen[name]).collect();
+    prop_assert!(starts.windows(2).all(|pair| pair[0] < pair[1]), "not in order of first use: {:?}", identifiers);
+    Ok(())
+}
+
+fn assert_tokens_cover(source: &str, output: &LexOutput) -> Result<(), TestCaseError> {
+    let mut end = 0;
+    for token in &output.tokens {
+        prop_assert!(token.span.start >= end, "overlapping token {:?}", token);
+        let gap = &source[end..token.span.start];
+        prop_assert!(gap.trim().is_empty() || gap.trim_start().starts_with("//") || gap.trim_start().starts_with("/*"), "skipped {:?}", gap);
+        prop_assert_eq!(&source[token.span.start..token.span.end], token.content.as_str());
+        end = token.span.end;
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn valid_programs_lex_cleanly(source in program()) {
+        let output = lex(&source);
+        prop_assert!(output.diagnostics.iter().all(|d| d.is_warning()), "{:?}", output.diagnostics);
+        prop_assert!(output.tokens.iter().all(|t| t.type_name != TokenType::Invalid));
+        assert_tokens_cover(&source, &output)?;
+
+        let trivia = lex_with_options(&source, LexerOptions { trivia: true, ..LexerOptions::default() });
+        prop_assert_eq!(trivia.tokens.iter().map(|t| t.content.as_str()).collect::<String>(), source.as_str());
+        let significant: Vec<_> = trivia.tokens.iter().filter(|t| !t.is_trivia()).cloned().collect();
+        prop_assert_eq!(significant, output.tokens.clone());
+
+        let parsed = parse(&output.tokens);
+        prop_assert!(parsed.diagnostics.is_empty(), "{:?}\n{}", parsed.diagnostics, source);
+    }
+
+    #[test]
+    fn identifiers_are_the_distinct_identifier_tokens(source in program()) {
+        let output = lex(&source);
+        assert_identifiers(&output)?;
+    }
+
+    #[test]
+    fn arbitrary_input_never_panics(source in "(\\PC|\\s){0,120}") {
+        let output = lex(&source);
+        assert_identifiers(&output)?;
+        for token in &output.tokens {
+            prop_assert_eq!(&source[token.span.start..token.span.end], token.content.as_str());
+        }
+        parse(&output.tokens);
+    }
+}
@@ -0,0 +1,65 @@
+use lab1::{lex, lex_with_options, source_stats, LexerOptions, TokenType};
+use proptest::prelude::*;
+use serde_json::json;
+
+const SOURCE: &str = "fn shvachka(a: i32) -> i32 {
+    let mut sk = a;
+    while sk > 0 && a != 1 {
+        if sk % 2 == 0 || sk > 10 { sk -= 1; } else { loop { sk -= 3; } }
+    }
+    for k in 0..sk { sk += $; } // коментар
+    sk
+}
+";
+
+#[test]
+fn counts_tokens_depth_and_complexity() {
+    let output = lex_with_options(SOURCE, LexerOptions { keep_comments: true, ..LexerOptions::default() });
+    let stats = source_stats(SOURCE, &output.tokens);
+    assert_eq!(stats.lines, 8);
+    assert_eq!(stats.tokens, output.tokens.len());
+    assert_eq!(stats.max_depth, 4);
+    assert_eq!(stats.complexity, 7);
+    assert_eq!(stats.invalid, 2);
+    assert_eq!(stats.invalid_ratio, 2.0 / stats.tokens as f64);
+    assert_eq!(stats.kinds[&TokenType::Comment], 1);
+    assert_eq!(stats.kinds[&TokenType::Logical], 2);
+    assert_eq!(stats.keywords["let"], 1);
+    assert_eq!(stats.keywords["if"], 1);
+    assert_eq!(stats.identifiers_by_frequency(), [("sk", 9), ("a", 3), ("k", 1), ("shvachka", 1)]);
+}
+
+#[test]
+fn serialises_to_json() {
+    let stats = source_stats("let a = a + 1;", &lex("let a = a + 1;").tokens);
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap(),
+        json!({
+            "lines": 1,
+            "tokens": 7,
+            "invalid": 0,
+            "invalid_ratio": 0.0,
+            "max_depth": 0,
+            "complexity": 1,
+            "kinds": { "Integer": 1, "Identifier": 2, "Arithmetic": 1, "KeyWord": 1, "Delimiter": 1, "Assignment": 1 },
+            "keywords": { "let": 1 },
+            "identifiers": { "a": 2 },
+        })
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn counts_are_consistent(source in "(\\PC|\\s){0,120}") {
+        let output = lex_with_options(&source, LexerOptions { trivia: true, ..LexerOptions::default() });
+        let stats = source_stats(&source, &output.tokens);
+        prop_assert_eq!(stats.kinds.values().sum::<usize>(), stats.tokens);
+        prop_assert_eq!(stats.identifiers.values().sum::<usize>(), stats.kinds.get(&TokenType::Identifier).copied().unwrap_or(0));
+        prop_assert_eq!(stats.keywords.values().sum::<usize>(), stats.kinds.get(&TokenType::KeyWord).copied().unwrap_or(0));
+        prop_assert!(!stats.kinds.contains_key(&TokenType::Whitespace) && !stats.kinds.contains_key(&TokenType::Newline));
+        prop_assert!(stats.complexity >= 1 && (0.0..=1.0).contains(&stats.invalid_ratio));
+        prop_assert!(stats.max_depth <= stats.kinds.get(&TokenType::Delimiter).copied().unwrap_or(0));
+    }
+}
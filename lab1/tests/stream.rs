@@ -0,0 +1,112 @@
+use lab1::{lex_with_options, IdentifierMode, LexerOptions, StreamLexer, TokenType};
+use std::io::{self, Read};
+
+const SOURCE: &str = r##"#[derive(Debug)]
+fn shvachka(a: f64, hvac: i32) -> f64 {
+    // рядок коментаря з кирилицею
+    let mut sk = sqrt(a) * 2.5e-3 + -12;
+    let s = "рядок \u{1F600} з escape\n";
+    let r = r#"raw "text" ще"#;
+    let c = 'ш';
+    /* nested /* block */ comment */
+    if sk >= 10 && hvac != 0 { sk += cos(a) / 3; } else { sk -= 0x1F; }
+    let сk = 'a;
+    println!("{}", 1..=3);
+    $ let unterminated = "end
+"##;
+
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    step: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.step.min(buf.len()).min(self.bytes.len());
+        buf[..n].copy_from_slice(&self.bytes[..n]);
+        self.bytes = &self.bytes[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn streaming_matches_full_lex_for_every_chunk_size() {
+    for unicode in [false, true] {
+        for (keep_comments, trivia) in [(false, false), (true, false), (false, true)] {
+            let identifiers = if unicode { IdentifierMode::Unicode } else { IdentifierMode::Restricted };
+            let options = LexerOptions { keep_comments, identifiers, trivia };
+            let expected = lex_with_options(SOURCE, options);
+
+            for (step, chunk_size) in [(1, 1), (1, 3), (2, 5), (3, 7), (5, 16), (64, 64), (4096, 4096)] {
+                let reader = Trickle { bytes: SOURCE.as_bytes(), step };
+                let mut lexer = StreamLexer::with_options(reader, options).chunk_size(chunk_size);
+                let tokens: Vec<_> = lexer.by_ref().collect::<io::Result<_>>().unwrap();
+                assert_eq!(tokens, expected.tokens, "step {} chunk {}", step, chunk_size);
+                assert_eq!(lexer.take_diagnostics(), expected.diagnostics, "step {} chunk {}", step, chunk_size);
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_keeps_the_buffer_bounded() {
+    let source = "let mut shvachka = sqrt(25) + 1;\n".repeat(20_000);
+    let mut lexer = StreamLexer::new(source.as_bytes()).chunk_size(256);
+    let mut count = 0;
+    while let Some(token) = lexer.next() {
+        token.unwrap();
+        count += 1;
+        assert!(lexer.buffered() <= 1024, "buffered {} bytes", lexer.buffered());
+    }
+    assert_eq!(count, 20_000 * 11);
+}
+
+#[test]
+fn streaming_rejects_invalid_utf8() {
+    let bytes = [b'l', b'e', b't', b' ', 0xD1];
+    let result: io::Result<Vec<_>> = StreamLexer::new(&bytes[..]).collect();
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let bytes = [b'x', 0xFF, b'y'];
+    let result: io::Result<Vec<_>> = StreamLexer::new(&bytes[..]).collect();
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn oversized_tokens_are_cut_with_a_diagnostic() {
+    let source = format!("let s = \"{}\";\nlet t = 1;\n", "ш".repeat(5_000));
+    let mut lexer = StreamLexer::new(source.as_bytes()).chunk_size(64).max_token_len(1001);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next() {
+        tokens.push(token.unwrap());
+        assert!(lexer.buffered() <= 2 * 1001 + 64, "buffered {} bytes", lexer.buffered());
+    }
+    let invalid = &tokens[3];
+    assert_eq!(invalid.type_name, TokenType::Invalid);
+    assert_eq!((invalid.span.start, invalid.span.end, invalid.content.len()), (8, 1009, 1001));
+    let diagnostics = lexer.take_diagnostics();
+    assert_eq!(diagnostics[0].to_string(), "token exceeds the maximum length of 1001 bytes");
+    assert_eq!(diagnostics[0].span(), invalid.span);
+    assert!(tokens.iter().all(|t| t.content.len() <= 1001));
+    assert!(diagnostics.iter().all(|e| e.span().end - e.span().start <= 1001));
+}
+
+#[test]
+fn long_skipped_trivia_is_not_an_oversized_token() {
+    let source = format!("{}let x = 1;", "// коментар\n   \n".repeat(2_000));
+    let mut lexer = StreamLexer::new(source.as_bytes()).chunk_size(64).max_token_len(256);
+    let tokens: Vec<_> = lexer.by_ref().collect::<io::Result<_>>().unwrap();
+    let expected = lex_with_options(&source, LexerOptions::default());
+    assert_eq!(tokens, expected.tokens);
+    assert_eq!(lexer.take_diagnostics(), expected.diagnostics);
+}
+
+#[test]
+fn confusable_tracking_is_capped() {
+    let options = LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() };
+    let source = "scope \u{455}\u{441}\u{43e}\u{440}\u{435} other \u{43e}ther";
+    let mut lexer = StreamLexer::with_options(source.as_bytes(), options).max_identifiers(1);
+    lexer.by_ref().for_each(|token| drop(token.unwrap()));
+    let messages: Vec<String> = lexer.take_diagnostics().iter().map(|e| e.to_string()).filter(|m| m.contains("confusable")).collect();
+    assert_eq!(messages, ["identifier `\u{455}\u{441}\u{43e}\u{440}\u{435}` is confusable with `scope`"]);
+}
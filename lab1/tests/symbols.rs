@@ -0,0 +1,117 @@
+use lab1::{lex_with_options, parse, resolve, IdentifierMode, LexerOptions, ResolveOutput, Symbol, SymbolError, SymbolKind, SymbolTable};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn resolve_source(source: &str) -> ResolveOutput {
+    let options = LexerOptions { identifiers: IdentifierMode::Unicode, ..LexerOptions::default() };
+    let parsed = parse(&lex_with_options(source, options).tokens);
+    assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+    resolve(&parsed.program)
+}
+
+fn symbols<'a>(table: &'a SymbolTable, name: &str) -> Vec<&'a Symbol> {
+    table.symbols.iter().filter(|s| s.name == name).collect()
+}
+
+fn positions(symbol: &Symbol) -> Vec<(usize, usize)> {
+    symbol.usages.iter().map(|s| (s.line, s.column)).collect()
+}
+
+fn messages(output: &ResolveOutput) -> Vec<(String, (usize, usize))> {
+    output.diagnostics.iter().map(|e| (e.to_string(), (e.span().line, e.span().column))).collect()
+}
+
+#[test]
+fn nested_blocks_open_nested_scopes() {
+    let output = resolve_source("fn main() {\n    let a = 1;\n    {\n        let b = a;\n        b;\n    }\n}\n");
+    let table = &output.table;
+    let (a, b) = (symbols(table, "a")[0], symbols(table, "b")[0]);
+    assert_eq!(table.scopes[b.scope].parent, Some(a.scope));
+    assert_eq!(table.scopes[b.scope].depth, table.scopes[a.scope].depth + 1);
+    assert_eq!(table.lookup(b.scope, "a").map(|id| table.symbols[id].span), Some(a.span));
+    assert_eq!(table.lookup(a.scope, "b"), None);
+    assert_eq!(table.symbols[table.lookup(0, "main").unwrap()].kind, SymbolKind::Function);
+    assert!(output.diagnostics.is_empty(), "{:?}", output.diagnostics);
+}
+
+#[test]
+fn shadowing_binds_uses_to_the_latest_declaration() {
+    let output = resolve_source("fn main() {\n    let x = 1;\n    let x = x + 1;\n    {\n        let x = 0;\n        x;\n    }\n    x;\n}\n");
+    let xs = symbols(&output.table, "x");
+    assert_eq!(xs.len(), 3);
+    assert_eq!(positions(xs[0]), [(3, 13)]);
+    assert_eq!(positions(xs[1]), [(8, 5)]);
+    assert_eq!(positions(xs[2]), [(6, 9)]);
+    assert!(output.diagnostics.is_empty(), "{:?}", output.diagnostics);
+}
+
+#[test]
+fn parameters_items_and_members_are_declared() {
+    let source = "struct Point { x: f64 }\nimpl Point { fn len(&self) -> f64 { self.x } }\nenum Shape { Dot }\nfn area(p: Point, mut k: f64) -> f64 {\n    k *= p.len();\n    let s = Shape::Dot;\n    k\n}\n";
+    let output = resolve_source(source);
+    let table = &output.table;
+    let kind = |name: &str| symbols(table, name).iter().map(|s| s.kind).collect::<Vec<_>>();
+    assert_eq!(kind("Point"), [SymbolKind::Struct]);
+    assert_eq!(kind("x"), [SymbolKind::Field]);
+    assert_eq!(kind("len"), [SymbolKind::Function]);
+    assert_eq!(kind("Dot"), [SymbolKind::Variant]);
+    assert_eq!(kind("p"), [SymbolKind::Parameter]);
+    let k = symbols(table, "k")[0];
+    assert!(k.mutable);
+    assert_eq!(k.ty.as_deref(), Some("f64"));
+    assert_eq!(positions(symbols(table, "Dot")[0]), [(6, 20)]);
+    assert_eq!(positions(symbols(table, "x")[0]), [(2, 42)]);
+    assert_eq!(messages(&output), [("unused variable `s`".to_string(), (6, 9))]);
+}
+
+#[test]
+fn undeclared_names_are_reported() {
+    let output = resolve_source("fn main() {\n    let a = b + 1;\n    {\n        let c = 2;\n    }\n    a + c;\n}\n");
+    assert_eq!(
+        messages(&output),
+        [
+            ("cannot find `b` in this scope".to_string(), (2, 13)),
+            ("cannot find `c` in this scope".to_string(), (6, 9)),
+            ("unused variable `c`".to_string(), (4, 13)),
+        ]
+    );
+    assert!(output.diagnostics.iter().any(|e| matches!(e, SymbolError::Undeclared { name, .. } if name == "b")));
+    assert!(!output.diagnostics[0].is_warning());
+}
+
+#[test]
+fn unused_variables_are_warnings() {
+    let output = resolve_source("fn main(self_like: i32, _skip: i32) {\n    let used = 1;\n    let unused = used;\n    for i in 0..3 {}\n}\n");
+    assert_eq!(
+        messages(&output),
+        [
+            ("unused variable `self_like`".to_string(), (1, 9)),
+            ("unused variable `unused`".to_string(), (3, 9)),
+            ("unused variable `i`".to_string(), (4, 9)),
+        ]
+    );
+    assert!(output.diagnostics.iter().all(SymbolError::is_warning));
+}
+
+#[test]
+fn usages_can_be_recorded_out_of_source_order() {
+    let output = resolve_source("fn main() {\n    let mut x = 1;\n    x = x + 1;\n}\n");
+    let x = symbols(&output.table, "x")[0];
+    assert_eq!(positions(x), [(3, 9), (3, 5)]);
+}
+
+#[test]
+fn symbols_table_lists_usages_in_source_order() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lab1"))
+        .args(["--format", "symbols", "--unicode"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"fn main() {\n    let mut x = 1;\n    x = x + 1;\n}\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row = stdout.lines().find(|line| line.starts_with("x ")).unwrap();
+    assert!(row.ends_with("2:13       3:5 3:9"), "{}", row);
+}
@@ -60,10 +60,10 @@ impl Lexer {
                     let mut look_ahead = String::new();
                     let mut temp_paren_level = 1;
                     let mut found_comma = false;
-                    let mut peek_chars = chars.clone();
+                    let peek_chars = chars.clone();
                     let mut found_close = false;
 
-                    while let Some(next_c) = peek_chars.next() {
+                    for next_c in peek_chars {
                         look_ahead.push(next_c);
                         match next_c {
                             '(' => temp_paren_level += 1,
@@ -74,11 +74,9 @@ impl Lexer {
                                     break;
                                 }
                             }
-                            ',' => {
-                                if temp_paren_level == 1 {
-                                    found_comma = true;
-                                    break;
-                                }
+                            ',' if temp_paren_level == 1 => {
+                                found_comma = true;
+                                break;
                             }
                             _ => {}
                         }
@@ -96,12 +94,10 @@ impl Lexer {
                 ')' => {
                     if is_expression {
                         current_word.push(c);
-                        if let Some(_) = paren_stack.pop() {
-                            if paren_stack.is_empty() {
-                                result.push(current_word.clone());
-                                current_word.clear();
-                                is_expression = false;
-                            }
+                        if paren_stack.pop().is_some() && paren_stack.is_empty() {
+                            result.push(current_word.clone());
+                            current_word.clear();
+                            is_expression = false;
                         }
                     } else {
                         if !current_word.is_empty() {
@@ -111,25 +107,20 @@ impl Lexer {
                         result.push(c.to_string());
                     }
                 }
-                ',' | ';' | '\'' | '.' | '?' | '!' | ':' | '"' => {
-                    if !is_expression {
-                        if !current_word.is_empty() {
-                            result.push(current_word.clone());
-                            current_word.clear();
-                        }
-                        result.push(c.to_string());
-                    } else {
-                        current_word.push(c);
+                '.' if current_word.ends_with(|c: char| c.is_ascii_digit()) && chars.peek().is_some_and(|c| c.is_ascii_digit()) => {
+                    current_word.push(c);
+                }
+                ',' | ';' | '\'' | '.' | '?' | '!' | ':' | '"' if !is_expression => {
+                    if !current_word.is_empty() {
+                        result.push(current_word.clone());
+                        current_word.clear();
                     }
+                    result.push(c.to_string());
                 }
-                ' ' => {
-                    if !is_expression {
-                        if !current_word.is_empty() {
-                            result.push(current_word.clone());
-                            current_word.clear();
-                        }
-                    } else {
-                        current_word.push(c);
+                ' ' if !is_expression => {
+                    if !current_word.is_empty() {
+                        result.push(current_word.clone());
+                        current_word.clear();
                     }
                 }
                 _ => {
@@ -158,13 +149,11 @@ impl Lexer {
                 i += 1;
                 continue;
             }
-            if RECTANGLE_NAME_REGEX.is_match(word) {
-                result.push(word.clone());
-            } else if POINT_NAME_REGEX.is_match(word) {
+            if RECTANGLE_NAME_REGEX.is_match(word) || POINT_NAME_REGEX.is_match(word) {
                 result.push(word.clone());
             } else if let Some(processed) = self.process_measurement(word, words.get(i + 1).map(|s| s.as_str())) {
                 result.push(processed);
-                if words.get(i + 1).map_or(false, |next| self.has_unit(Some(next))) {
+                if words.get(i + 1).is_some_and(|next| self.has_unit(Some(next))) {
                     i += 1;
                 }
             } else if let Some(expr) = self.try_parse_expression(word) {
@@ -264,7 +253,7 @@ impl ExpressionEvaluator {
                     chars.next();
                     if tokens.is_empty() || matches!(tokens.last(), Some(Token::LeftParen)) {
                         if let Some(&next_char) = chars.peek() {
-                            if next_char.is_digit(10) {
+                            if next_char.is_ascii_digit() {
                                 let num = self.parse_number(&mut chars)?;
                                 tokens.push(Token::Number(-num));
                                 continue;
@@ -302,7 +291,7 @@ impl ExpressionEvaluator {
         let mut number_str = String::new();
 
         while let Some(&c) = chars.peek() {
-            if c.is_digit(10) || c == '.' {
+            if c.is_ascii_digit() || c == '.' {
                 number_str.push(c);
                 chars.next();
             } else {
@@ -1,13 +1,15 @@
 mod lexer;
 
+use geometry_core::{approx_eq, Dimensions, Point, Size, DEFAULT_EPSILON};
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::Path;
 use serde::{Deserialize, Serialize};
 use serde_json::to_writer;
 use crate::lexer::Lexer;
@@ -26,6 +28,20 @@ pub enum ParseError {
     IncorrectInput(String),
     RectangleNotFound(String),
 }
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IncorrectName(message) => write!(f, "incorrect name: {}", message),
+            IncorrectValues(message) => write!(f, "incorrect values: {}", message),
+            IncorrectCoordinates(message) => write!(f, "incorrect coordinates: {}", message),
+            IncorrectInput(message) => write!(f, "incorrect input: {}", message),
+            RectangleNotFound(message) => write!(f, "rectangle not found: {}", message),
+        }
+    }
+}
+
+impl Error for ParseError {}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 enum RectangleProperties {
     Perimeter(f64),
@@ -34,57 +50,34 @@ enum RectangleProperties {
     IsIntersection(bool),
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Point {
-    x: i32,
-    y: i32,
-    name: String,
-}
-#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rectangle {
-    w: i32,
-    h: i32,
+    #[serde(flatten)]
+    size: Size,
     points: Vec<Point>,
     name: String,
     properties: Vec<RectangleProperties>,
 }
 
 impl Rectangle {
-    pub fn new(x: i32, y: i32, w: i32, h: i32, name: &String) -> Result<Rectangle, ParseError> {
+    pub fn new(x: f64, y: f64, w: f64, h: f64, name: &String) -> Result<Rectangle, ParseError> {
         let point_names = Self::process_name(name)?;
 
-        let p1 = Point {
-            x,
-            y,
-            name: point_names[0].clone(),
-        };
-        let p2 = Point {
-            x,
-            y: y + h,
-            name: point_names[1].clone(),
-        };
-        let p3 = Point {
-            x: x + w,
-            y: y + h,
-            name: point_names[2].clone(),
-        };
-        let p4 = Point {
-            x: x + w,
-            y,
-            name: point_names[3].clone(),
-        };
-
-        let points = vec![p1, p2, p3, p4];
+        let points = vec![
+            Point::new(x, y, &point_names[0]),
+            Point::new(x, y + h, &point_names[1]),
+            Point::new(x + w, y + h, &point_names[2]),
+            Point::new(x + w, y, &point_names[3]),
+        ];
 
         Ok(Rectangle {
-            w,
-            h,
+            size: Size::new(w, h),
             points,
             name: name.clone(),
             properties: vec![]
         })
     }
 
-    pub fn new_from_coords(name: &String, coords: &[(i32, i32)]) -> Result<Rectangle, ParseError> {
+    pub fn new_from_coords(name: &String, coords: &[(f64, f64)], epsilon: f64) -> Result<Rectangle, ParseError> {
         let point_names = Self::process_name(name)?;
 
         if coords.len() != 4 {
@@ -93,28 +86,25 @@ impl Rectangle {
             ));
         }
 
-        let p1 = Point { x: coords[0].0, y: coords[0].1, name: point_names[0].clone() };
-        let p2 = Point { x: coords[1].0, y: coords[1].1, name: point_names[1].clone() };
-        let p3 = Point { x: coords[2].0, y: coords[2].1, name: point_names[2].clone() };
-        let p4 = Point { x: coords[3].0, y: coords[3].1, name: point_names[3].clone() };
-
-        let side1 = ((p1.x - p2.x).pow(2) + (p1.y - p2.y).pow(2)) as f64;
-        let side2 = ((p2.x - p3.x).pow(2) + (p2.y - p3.y).pow(2)) as f64;
-        let side3 = ((p3.x - p4.x).pow(2) + (p3.y - p4.y).pow(2)) as f64;
-        let side4 = ((p4.x - p1.x).pow(2) + (p4.y - p1.y).pow(2)) as f64;
-
-        if (side1 - side3).abs() > 0.001 || (side2 - side4).abs() > 0.001 {
+        let p1 = Point::new(coords[0].0, coords[0].1, &point_names[0]);
+        let p2 = Point::new(coords[1].0, coords[1].1, &point_names[1]);
+        let p3 = Point::new(coords[2].0, coords[2].1, &point_names[2]);
+        let p4 = Point::new(coords[3].0, coords[3].1, &point_names[3]);
+
+        let w = p1.distance(&p4);
+        let h = p1.distance(&p2);
+        if !approx_eq(w, p2.distance(&p3), epsilon)
+            || !approx_eq(h, p3.distance(&p4), epsilon)
+            || !approx_eq(p1.distance(&p3), p2.distance(&p4), epsilon)
+        {
             return Err(IncorrectInput(
                 "Provided coordinates do not form a rectangle.".to_string(),
             ));
         }
 
-        let w = (p2.x - p1.x).abs().max((p4.x - p1.x).abs());
-        let h = (p3.y - p1.y).abs().max((p4.y - p1.y).abs());
 
         Ok(Rectangle {
-            w,
-            h,
+            size: Size::new(w, h),
             points: vec![p1, p2, p3, p4],
             name: name.clone(),
             properties: vec![]
@@ -122,7 +112,7 @@ impl Rectangle {
     }
 
 
-    pub fn new_with_ratio(name: &String, ratio: (u32, u32), length: u32, x: i32, y: i32) -> Result<Rectangle, ParseError> {
+    pub fn new_with_ratio(name: &String, ratio: (u32, u32), length: f64, x: f64, y: f64) -> Result<Rectangle, ParseError> {
         Self::process_name(name)?;
 
         if ratio.0 == 0 || ratio.1 == 0 {
@@ -131,10 +121,9 @@ impl Rectangle {
             ));
         }
 
-        let total_ratio = ratio.0 + ratio.1;
-        let unit_length = length as f32 / total_ratio as f32;
-        let w = (unit_length * ratio.0 as f32) as i32;
-        let h = (unit_length * ratio.1 as f32) as i32;
+        let unit_length = length / (ratio.0 as f64 + ratio.1 as f64);
+        let w = unit_length * ratio.0 as f64;
+        let h = unit_length * ratio.1 as f64;
 
         Rectangle::new(x, y, w, h, name)
     }
@@ -168,25 +157,25 @@ impl Rectangle {
         self.points.iter().any(|p| p.name == *name)
     }
 
-    pub fn area(&mut self) -> i32 {
+    pub fn area(&mut self) -> f64 {
         self.properties.retain(|p| !matches!(p, Area(_)));
-        let area = (self.w.abs() * self.h.abs()) as f64;
+        let area = Dimensions::area(self);
         self.properties.push(Area(area));
-        area as i32
+        area
     }
 
-    pub fn perimeter(&mut self) -> i32 {
+    pub fn perimeter(&mut self) -> f64 {
         self.properties.retain(|p| !matches!(p, Perimeter(_)));
-        let perimeter = 2.0 * (self.w.abs() as f64 + self.h.abs() as f64);
+        let perimeter = Dimensions::perimeter(self);
         self.properties.push(Perimeter(perimeter));
-        perimeter as i32
+        perimeter
     }
 
-    pub fn diagonal(&mut self) -> i32 {
+    pub fn diagonal(&mut self) -> f64 {
         self.properties.retain(|p| !matches!(p, Diagonal(_)));
-        let diagonal = ((self.w.pow(2) + self.h.pow(2)) as f64).sqrt();
+        let diagonal = Dimensions::diagonal(self);
         self.properties.push(Diagonal(diagonal));
-        diagonal as i32
+        diagonal
     }
 
     fn update_calculations(&mut self) {
@@ -201,52 +190,52 @@ impl Rectangle {
         }
     }
 
-    pub fn get_position(&self) -> (i32, i32) {
+    pub fn get_position(&self) -> (f64, f64) {
         (self.points[0].x, self.points[0].y)
     }
 
-    pub fn move_rectangle(&mut self, dx: i32, dy: i32) {
+    pub fn move_rectangle(&mut self, dx: f64, dy: f64) {
         for point in &mut self.points {
             point.x += dx;
             point.y += dy;
         }
     }
 
-    pub fn move_to(&mut self, x: i32, y: i32) {
+    pub fn move_to(&mut self, x: f64, y: f64) {
         self.points[0].x = x;
         self.points[0].y = y;
 
         self.points[1].x = x;
-        self.points[1].y = y + self.h;
+        self.points[1].y = y + self.size.height;
 
-        self.points[2].x = x + self.w;
-        self.points[2].y = y + self.h;
+        self.points[2].x = x + self.size.width;
+        self.points[2].y = y + self.size.height;
 
-        self.points[3].x = x + self.w;
+        self.points[3].x = x + self.size.width;
         self.points[3].y = y;
     }
 
-    pub fn resize_rectangle(&mut self, factor: f32) {
-        self.w = (self.w as f32 * factor) as i32;
-        self.h = (self.h as f32 * factor) as i32;
+    pub fn resize_rectangle(&mut self, factor: f64) {
+        self.size.scale(factor);
         let pos = self.get_position();
         for point in &mut self.points {
-            point.x = (pos.0 as f32 + (point.x - pos.0) as f32 * factor) as i32;
-            point.y = (pos.1 as f32 + (point.y - pos.1) as f32 * factor) as i32;
+            point.x = pos.0 + (point.x - pos.0) * factor;
+            point.y = pos.1 + (point.y - pos.1) * factor;
         }
         self.update_calculations();
     }
 
-    pub fn rotate_rectangle(&mut self, angle: f32, center_x: i32, center_y: i32) {
-        let angle_rad = angle.to_radians();
+    pub fn rotate_rectangle(&mut self, angle: f64, center_x: f64, center_y: f64) {
+        let (sin, cos) = angle.to_radians().sin_cos();
         for point in &mut self.points {
-            let translated_x = (point.x - center_x) as f32;
-            let translated_y = (point.y - center_y) as f32;
-            point.x = center_x
-                + (translated_x * angle_rad.cos() - translated_y * angle_rad.sin()).round() as i32;
-            point.y = center_y
-                + (translated_x * angle_rad.sin() + translated_y * angle_rad.cos()).round() as i32;
+            let translated_x = point.x - center_x;
+            let translated_y = point.y - center_y;
+            point.x = center_x + translated_x * cos - translated_y * sin;
+            point.y = center_y + translated_x * sin + translated_y * cos;
         }
+        let (side_x, side_y) = (self.points[0].distance(&self.points[3]), self.points[0].distance(&self.points[1]));
+        let horizontal = (self.points[3].x - self.points[0].x).abs() >= (self.points[3].y - self.points[0].y).abs();
+        self.size = if horizontal { Size::new(side_x, side_y) } else { Size::new(side_y, side_x) };
     }
 
     pub fn reflect_rectangle(&mut self, axis: char) {
@@ -260,13 +249,13 @@ impl Rectangle {
         }
     }
 
-    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+    pub fn intersection(&self, other: &Rectangle, epsilon: f64) -> Option<Rectangle> {
         let x1 = self.points[0].x.max(other.points[0].x);
         let y1 = self.points[0].y.max(other.points[0].y);
         let x2 = self.points[2].x.min(other.points[2].x);
         let y2 = self.points[1].y.min(other.points[1].y);
 
-        if x1 < x2 && y1 < y2 {
+        if x1 < x2 && !approx_eq(x1, x2, epsilon) && y1 < y2 && !approx_eq(y1, y2, epsilon) {
             let intersection_name = format!(
                 "I{}{}",
                 self.name.chars().next().unwrap_or('X'),
@@ -274,18 +263,14 @@ impl Rectangle {
             );
 
             let points = vec![
-                Point { x: x1, y: y1, name: format!("{}1", intersection_name) },
-                Point { x: x1, y: y2, name: format!("{}2", intersection_name) },
-                Point { x: x2, y: y2, name: format!("{}3", intersection_name) },
-                Point { x: x2, y: y1, name: format!("{}4", intersection_name) },
+                Point::new(x1, y1, &format!("{}1", intersection_name)),
+                Point::new(x1, y2, &format!("{}2", intersection_name)),
+                Point::new(x2, y2, &format!("{}3", intersection_name)),
+                Point::new(x2, y1, &format!("{}4", intersection_name)),
             ];
 
-            let w = x2 - x1;
-            let h = y2 - y1;
-
             Some(Rectangle {
-                w,
-                h,
+                size: Size::new(x2 - x1, y2 - y1),
                 points,
                 name: intersection_name,
                 properties: vec![RectangleProperties::IsIntersection(true)],
@@ -324,7 +309,21 @@ impl Rectangle {
     }
 }
 
-pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
+impl Dimensions for Rectangle {
+    fn width(&self) -> f64 {
+        self.size.width
+    }
+
+    fn height(&self) -> f64 {
+        self.size.height
+    }
+}
+
+pub fn parse(source: &str, path: &Path) -> Result<(), ParseError> {
+    parse_with_epsilon(source, path, DEFAULT_EPSILON)
+}
+
+pub fn parse_with_epsilon(source: &str, path: &Path, epsilon: f64) -> Result<(), ParseError> {
     //println!("source: {:?}", source);
     let processed = Lexer::new(source).process();
     //println!("processed: {:?}", processed);
@@ -354,7 +353,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
         }
     }
 
-    fn parse_coordinate_pair(pair: Pair<Rule>) -> Result<(i32, i32), ParseError> {
+    fn parse_coordinate_pair(pair: Pair<Rule>) -> Result<(f64, f64), ParseError> {
         if pair.as_rule() != Rule::coordinate_pair && pair.as_rule() != Rule::vector {
             return Err(IncorrectInput(
                 format!(
@@ -368,19 +367,19 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
         let mut coords = pair.into_inner();
         let x = coords
             .next()
-            .and_then(|p| p.as_str().parse::<i32>().ok())
+            .and_then(|p| p.as_str().parse::<f64>().ok())
             .ok_or_else(|| {
                 IncorrectInput("Incorrect X coordinate format.".to_string())
             })?;
         let y = coords
             .next()
-            .and_then(|p| p.as_str().parse::<i32>().ok())
+            .and_then(|p| p.as_str().parse::<f64>().ok())
             .ok_or_else(|| {
                 IncorrectInput("Incorrect Y coordinate format.".to_string())
             })?;
         Ok((x, y))
     }
-    fn parse_coordinate_list(coordinate_list: Pair<Rule>) -> Result<Vec<(i32, i32)>, ParseError> {
+    fn parse_coordinate_list(coordinate_list: Pair<Rule>) -> Result<Vec<(f64, f64)>, ParseError> {
         if coordinate_list.as_rule() != Rule::coordinate_list {
             return Err(IncorrectInput(
                 format!(
@@ -417,7 +416,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                     match coords_list.as_rule() {
                         Rule::coordinate_list => {
                             let coords = parse_coordinate_list(coords_list)?;
-                            let rect = Rectangle::new_from_coords(&name, &coords)?;
+                            let rect = Rectangle::new_from_coords(&name, &coords, epsilon)?;
                             rectangles.insert(name.clone(), rect);
                             last_rectangle = Some(name.clone());
                         }
@@ -426,19 +425,19 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                             let height = params_inner
                                 .find(|p| p.as_rule() == Rule::height)
                                 .and_then(|p| p.into_inner().next())
-                                .and_then(|p| p.as_str().parse::<i32>().ok())
+                                .and_then(|p| p.as_str().parse::<f64>().ok())
                                 .ok_or_else(|| IncorrectInput("Incorrect height".to_string()))?;
 
                             let width = params_inner
                                 .find(|p| p.as_rule() == Rule::width)
                                 .and_then(|p| p.into_inner().next())
-                                .and_then(|p| p.as_str().parse::<i32>().ok())
+                                .and_then(|p| p.as_str().parse::<f64>().ok())
                                 .ok_or_else(|| IncorrectInput("Incorrect width".to_string()))?;
 
                             let (x, y) = if let Some(coord_pair) = inner.next() {
                                 parse_coordinate_pair(coord_pair)?
                             } else {
-                                (0, 0)
+                                (0.0, 0.0)
                             };
 
                             let rect = Rectangle::new(x, y, width, height, &name)?;
@@ -448,7 +447,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                         _ => {},
                     }
                 } else {
-                    let rect = Rectangle::new(0, 0, 10, 20, &name)?;
+                    let rect = Rectangle::new(0.0, 0.0, 10.0, 20.0, &name)?;
                     rectangles.insert(name.clone(), rect);
                     last_rectangle = Some(name.clone());
                 }
@@ -516,7 +515,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                 let factor = inner
                     .next()
                     .filter(|p| p.as_rule() == Rule::coefficient)
-                    .and_then(|p| p.as_str().parse::<f32>().ok())
+                    .and_then(|p| p.as_str().parse::<f64>().ok())
                     .ok_or_else(|| IncorrectInput("The coefficient factor not found.".to_string()))?;
 
                 let rect = rectangles
@@ -530,7 +529,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                 let name = get_rectangle_name(&mut inner, &last_rectangle)?;
                 let angle = inner
                     .next()
-                    .and_then(|p| p.into_inner().next().unwrap().as_str().parse::<f32>().ok())
+                    .and_then(|p| p.into_inner().next().unwrap().as_str().parse::<f64>().ok())
                     .ok_or_else(|| IncorrectInput("The angle not found or incorrect.".to_string()))?;
 
                 let center = inner
@@ -589,7 +588,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                     .next()
                     .filter(|p| p.as_rule() == Rule::length)
                     .ok_or(IncorrectInput("The length not found or incorrect.".to_string()))?.into_inner();
-                let length = length_pair.next().unwrap().as_str().parse::<u32>().unwrap();
+                let length = length_pair.next().unwrap().as_str().parse::<f64>().unwrap();
 
                 let coordinate_pair = inner
                     .next()
@@ -610,7 +609,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
                     None => return Err(IncorrectInput("The rectangle name not found.".to_string()))
                 };
                 let name2 = get_rectangle_name(&mut inner, &last_rectangle)?;
-                let intersection = Rectangle::intersection(rectangles.get(&name1).unwrap(), rectangles.get(&name2).unwrap());
+                let intersection = Rectangle::intersection(rectangles.get(&name1).unwrap(), rectangles.get(&name2).unwrap(), epsilon);
                 match intersection {
                     None => {
                         println!("Intersection of rectangles {} and {} not found", name1, name2);
@@ -657,7 +656,7 @@ pub fn parse(source: &str, path: &PathBuf) -> Result<(), ParseError> {
     save_rectangles_to_json(&rectangles_vec, path).unwrap();
     Ok(())
 }
-fn save_rectangles_to_json(rectangles: &Vec<Rectangle>, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+fn save_rectangles_to_json(rectangles: &[Rectangle], path: &Path) -> Result<(), Box<dyn Error>> {
     let file = File::create(path.join("rectangles.json"))?;
     to_writer(file, &rectangles)?;
     Ok(())
@@ -0,0 +1,83 @@
+use geometry_core::{approx_eq, DEFAULT_EPSILON};
+use lab2::Rectangle;
+use serde_json::{json, Value};
+
+fn points(rect: &Rectangle) -> Vec<(f64, f64)> {
+    let value = serde_json::to_value(rect).unwrap();
+    value["points"].as_array().unwrap().iter().map(|p| (p["x"].as_f64().unwrap(), p["y"].as_f64().unwrap())).collect()
+}
+
+#[test]
+fn measurements_are_exact() {
+    let mut rect = Rectangle::new(0.5, -1.25, 3.0, 1.5, &"ABCD".to_string()).unwrap();
+    rect.resize_rectangle(1.5);
+    assert_eq!(rect.area(), 10.125);
+    assert_eq!(rect.perimeter(), 13.5);
+    assert_eq!(rect.diagonal(), 4.5f64.hypot(2.25));
+    assert_eq!(points(&rect), vec![(0.5, -1.25), (0.5, 1.0), (5.0, 1.0), (5.0, -1.25)]);
+
+    let value = serde_json::to_value(&rect).unwrap();
+    assert_eq!((value["w"].clone(), value["h"].clone()), (json!(4.5), json!(2.25)));
+    assert_eq!(value["properties"][0], json!({ "Area": 10.125 }));
+}
+
+#[test]
+fn repeated_rotations_do_not_drift() {
+    let mut rect = Rectangle::new(1.0, 2.0, 7.0, 3.0, &"ABCD".to_string()).unwrap();
+    let before = points(&rect);
+    for _ in 0..360 {
+        rect.rotate_rectangle(1.0, 0.0, 0.0);
+    }
+    for ((x, y), (ex, ey)) in points(&rect).into_iter().zip(before) {
+        assert!(approx_eq(x, ex, 1e-9) && approx_eq(y, ey, 1e-9), "({}, {}) drifted from ({}, {})", x, y, ex, ey);
+    }
+}
+
+#[test]
+fn rotated_rectangles_move_with_their_new_dimensions() {
+    let mut rect = Rectangle::new(1.0, 2.0, 7.0, 3.0, &"ABCD".to_string()).unwrap();
+    rect.rotate_rectangle(90.0, 1.0, 2.0);
+    let value = serde_json::to_value(&rect).unwrap();
+    assert!(approx_eq(value["w"].as_f64().unwrap(), 3.0, 1e-9) && approx_eq(value["h"].as_f64().unwrap(), 7.0, 1e-9));
+    rect.move_to(0.0, 0.0);
+    for ((x, y), (ex, ey)) in points(&rect).into_iter().zip([(0.0, 0.0), (0.0, 7.0), (3.0, 7.0), (3.0, 0.0)]) {
+        assert!(approx_eq(x, ex, 1e-9) && approx_eq(y, ey, 1e-9), "({}, {}) differs from ({}, {})", x, y, ex, ey);
+    }
+    assert!(approx_eq(rect.area(), 21.0, 1e-9));
+}
+
+#[test]
+fn ratio_rectangles_keep_fractions() {
+    let mut rect = Rectangle::new_with_ratio(&"M0H1LA".to_string(), (4, 5), 200.0, -10.0, 20.0).unwrap();
+    assert!(approx_eq(rect.area(), 800.0 / 9.0 * 1000.0 / 9.0, DEFAULT_EPSILON));
+    assert!(approx_eq(rect.perimeter(), 400.0, DEFAULT_EPSILON));
+}
+
+#[test]
+fn coordinates_are_compared_with_the_epsilon() {
+    let name = "ABCD".to_string();
+    let rotated = [(0.0, 0.0), (-1.0, 3.0f64.sqrt()), (3.0f64.sqrt() - 1.0, 1.0 + 3.0f64.sqrt()), (3.0f64.sqrt(), 1.0)];
+    let mut rect = Rectangle::new_from_coords(&name, &rotated, DEFAULT_EPSILON).unwrap();
+    assert!(approx_eq(rect.area(), 4.0, DEFAULT_EPSILON));
+
+    let skewed = [(0.0, 0.0), (0.0, 2.0), (3.0, 2.001), (3.0, 0.0)];
+    assert!(Rectangle::new_from_coords(&name, &skewed, DEFAULT_EPSILON).is_err());
+    assert!(Rectangle::new_from_coords(&name, &skewed, 1e-3).is_ok());
+    let parallelogram = [(0.0, 0.0), (1.0, 2.0), (4.0, 2.0), (3.0, 0.0)];
+    assert!(Rectangle::new_from_coords(&name, &parallelogram, DEFAULT_EPSILON).is_err());
+}
+
+#[test]
+fn touching_rectangles_do_not_intersect() {
+    let a = Rectangle::new(0.0, 0.0, 2.0, 2.0, &"ABCD".to_string()).unwrap();
+    let b = Rectangle::new(1.9999, 0.0, 2.0, 2.0, &"EFGH".to_string()).unwrap();
+    assert!(a.intersection(&b, 1e-3).is_none());
+    let overlap = a.intersection(&b, DEFAULT_EPSILON).unwrap();
+    let value: Value = serde_json::to_value(&overlap).unwrap();
+    assert!(approx_eq(value["w"].as_f64().unwrap(), 0.0001, 1e-9));
+
+    let far = Rectangle::new(1e6, 0.0, 2.0, 2.0, &"IJKL".to_string()).unwrap();
+    let grazing = Rectangle::new(1e6 + 1.9999999, 0.0, 2.0, 2.0, &"MNOP".to_string()).unwrap();
+    assert!(far.intersection(&grazing, DEFAULT_EPSILON).is_none());
+    assert!(far.intersection(&grazing, 1e-15).is_some());
+}
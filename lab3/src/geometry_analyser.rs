@@ -120,87 +120,80 @@ impl GeometryTaskAnalyser {
                         }
                     }
                 }
-                "діагональ" => {
-                    if !Self::keyword_before_number(&significant_words, i + 1) {
-                        if Self::word_before_next_data("сторона", &significant_words, i + 1)
-                            || Self::word_before_next_data("вона", &significant_words, i + 1)
-                        {
-                            if Self::word_before_next_data("кут", &significant_words, i + 2) {
-                                if in_target {
-                                    target_data.push(RectTarget::SideXDiagonalAngle);
-                                } else if let Some(value) = GeometryTaskAnalyser::find_next_number(
-                                    &significant_words,
-                                    i + 1,
-                                ) {
-                                    task_data.insert(
-                                        "side_diagonal_angle".to_string(),
-                                        Some(RectData::SideXDiagonalAngle(value.0)),
-                                    );
-                                }
+                "діагональ" if !Self::keyword_before_number(&significant_words, i + 1) => {
+                    if Self::word_before_next_data("сторона", &significant_words, i + 1)
+                        || Self::word_before_next_data("вона", &significant_words, i + 1)
+                    {
+                        if Self::word_before_next_data("кут", &significant_words, i + 2) {
+                            if in_target {
+                                target_data.push(RectTarget::SideXDiagonalAngle);
+                            } else if let Some(value) = GeometryTaskAnalyser::find_next_number(
+                                &significant_words,
+                                i + 1,
+                            ) {
+                                task_data.insert(
+                                    "side_diagonal_angle".to_string(),
+                                    Some(RectData::SideXDiagonalAngle(value.0)),
+                                );
                             }
-                        } else if Self::word_before_next_data(
-                            "перетинатися",
-                            &significant_words,
-                            i + 1,
-                        ) {
-                            if Self::word_before_next_data("кут", &significant_words, i + 2) {
-                                if in_target {
-                                    target_data.push(RectTarget::DiagonalDiagonalAngle);
-                                } else if let Some(value) = GeometryTaskAnalyser::find_next_number(
-                                    &significant_words,
-                                    i + 1,
-                                ) {
-                                    task_data.insert(
-                                        "diagonal_angle".to_string(),
-                                        Some(RectData::DiagonalDiagonalAngle(value.0)),
-                                    );
-                                    i += 1;
-                                }
+                        }
+                    } else if Self::word_before_next_data(
+                        "перетинатися",
+                        &significant_words,
+                        i + 1,
+                    ) {
+                        if Self::word_before_next_data("кут", &significant_words, i + 2) {
+                            if in_target {
+                                target_data.push(RectTarget::DiagonalDiagonalAngle);
+                            } else if let Some(value) = GeometryTaskAnalyser::find_next_number(
+                                &significant_words,
+                                i + 1,
+                            ) {
+                                task_data.insert(
+                                    "diagonal_angle".to_string(),
+                                    Some(RectData::DiagonalDiagonalAngle(value.0)),
+                                );
+                                i += 1;
                             }
-                        } else if in_target {
-                            target_data.push(RectTarget::Diagonal);
-                        } else if let Some(value) =
-                            GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
-                        {
-                            task_data
-                                .insert("diagonal".to_string(), Some(RectData::Diagonal(value.0)));
                         }
+                    } else if in_target {
+                        target_data.push(RectTarget::Diagonal);
+                    } else if let Some(value) =
+                        GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
+                    {
+                        task_data
+                            .insert("diagonal".to_string(), Some(RectData::Diagonal(value.0)));
                     }
                 }
-                "відноситися" | "співвідношення" | "відношення" => {
-                    if !Self::keyword_before_number(&significant_words, i + 1) {
-                        if let Some(x) =
-                            GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
-                        {
-                            if !Self::keyword_before_number(&significant_words, x.1 + 1) {
-                                if let Some(y) = GeometryTaskAnalyser::find_next_number(
-                                    &significant_words,
-                                    x.1 + 1,
-                                ) {
-                                    task_data.insert(
-                                        "ratio".to_string(),
-                                        Some(RectData::Ratio(x.0, y.0)),
-                                    );
-                                    i += 1;
-                                }
+                "відноситися" | "співвідношення" | "відношення" if !Self::keyword_before_number(&significant_words, i + 1) => {
+                    if let Some(x) =
+                        GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
+                    {
+                        if !Self::keyword_before_number(&significant_words, x.1 + 1) {
+                            if let Some(y) = GeometryTaskAnalyser::find_next_number(
+                                &significant_words,
+                                x.1 + 1,
+                            ) {
+                                task_data.insert(
+                                    "ratio".to_string(),
+                                    Some(RectData::Ratio(x.0, y.0)),
+                                );
+                                i += 1;
                             }
                         }
                     }
                 }
-                "кут" => {
-                    if Self::word_before_next_data("діагональ", &significant_words, i)
+                "кут" if Self::word_before_next_data("діагональ", &significant_words, i) => {
+                    if in_target {
+                        target_data.push(RectTarget::DiagonalDiagonalAngle);
+                    } else if let Some(value) =
+                        GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
                     {
-                        if in_target {
-                            target_data.push(RectTarget::DiagonalDiagonalAngle);
-                        } else if let Some(value) =
-                            GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
-                        {
-                            task_data.insert(
-                                "diagonal_angle".to_string(),
-                                Some(RectData::DiagonalDiagonalAngle(value.0)),
-                            );
-                            i += 1;
-                        }
+                        task_data.insert(
+                            "diagonal_angle".to_string(),
+                            Some(RectData::DiagonalDiagonalAngle(value.0)),
+                        );
+                        i += 1;
                     }
                 }
                 "менший" => {
@@ -211,14 +204,11 @@ impl GeometryTaskAnalyser {
                             GeometryTaskAnalyser::find_next_number(&significant_words, i + 2)
                         {
                             match task_data.get("sideX").unwrap() {
-                                Some(_) => match task_data.get("sideY").unwrap() {
-                                    None => {
-                                        task_data.insert(
-                                            "sideY".to_string(),
-                                            Some(RectData::SideY(value.0)),
-                                        );
-                                    }
-                                    _ => {}
+                                Some(_) => if task_data.get("sideY").unwrap().is_none() {
+                                    task_data.insert(
+                                        "sideY".to_string(),
+                                        Some(RectData::SideY(value.0)),
+                                    );
                                 },
                                 None => {
                                     task_data.insert(
@@ -243,14 +233,11 @@ impl GeometryTaskAnalyser {
                             GeometryTaskAnalyser::find_next_number(&significant_words, i + 2)
                         {
                             match task_data.get("sideX").unwrap() {
-                                Some(_) => match task_data.get("sideY").unwrap() {
-                                    None => {
-                                        task_data.insert(
-                                            "sideY".to_string(),
-                                            Some(RectData::SideY(value.0)),
-                                        );
-                                    }
-                                    _ => {}
+                                Some(_) => if task_data.get("sideY").unwrap().is_none() {
+                                    task_data.insert(
+                                        "sideY".to_string(),
+                                        Some(RectData::SideY(value.0)),
+                                    );
                                 },
                                 None => {
                                     task_data.insert(
@@ -341,20 +328,18 @@ impl GeometryTaskAnalyser {
                         );
                     }
                 }
-                "коло" => {
-                    if Self::word_before_next_data("площа", &significant_words, i + 1) {
-                        if in_target {
-                            target_data.push(RectTarget::CircumscribedCircleArea);
-                            i += 2;
-                        } else if let Some(s) =
-                            GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
-                        {
-                            task_data.insert(
-                                "circle_area".to_string(),
-                                Some(RectData::CircumscribedCircleArea(s.0)),
-                            );
-                            i = s.1;
-                        }
+                "коло" if Self::word_before_next_data("площа", &significant_words, i + 1) => {
+                    if in_target {
+                        target_data.push(RectTarget::CircumscribedCircleArea);
+                        i += 2;
+                    } else if let Some(s) =
+                        GeometryTaskAnalyser::find_next_number(&significant_words, i + 1)
+                    {
+                        task_data.insert(
+                            "circle_area".to_string(),
+                            Some(RectData::CircumscribedCircleArea(s.0)),
+                        );
+                        i = s.1;
                     }
                 }
                 "обчислити" | "знайти" => {
@@ -367,38 +352,38 @@ impl GeometryTaskAnalyser {
 
         (task_data, target_data)
     }
-    fn find_next_number(words: &Vec<String>, start_idx: usize) -> Option<(f64, usize)> {
-        for i in start_idx..std::cmp::min(start_idx + 10, words.len()) {
-            if let Ok(value) = words[i].parse::<f64>() {
-                return Some((value, i));
-            }
-        }
-        None
+    fn find_next_number(words: &[String], start_idx: usize) -> Option<(f64, usize)> {
+        words
+            .iter()
+            .enumerate()
+            .skip(start_idx)
+            .take(10)
+            .find_map(|(i, word)| word.parse::<f64>().ok().map(|value| (value, i)))
     }
 
-    fn word_before_next_data(word: &str, words: &Vec<String>, start_idx: usize) -> bool {
-        for i in start_idx..std::cmp::min(start_idx + 5, words.len()) {
-            if *&words[i].eq(word) {
+    fn word_before_next_data(word: &str, words: &[String], start_idx: usize) -> bool {
+        for next in words.iter().skip(start_idx).take(5) {
+            if next.eq(word) {
                 return true;
             }
-            if let Ok(_) = words[i].parse::<f64>() {
+            if next.parse::<f64>().is_ok() {
                 return false;
             }
         }
         false
     }
 
-    fn keyword_before_number(words: &Vec<String>, start_idx: usize) -> bool {
-        for i in start_idx..std::cmp::min(start_idx + 3, words.len()) {
+    fn keyword_before_number(words: &[String], start_idx: usize) -> bool {
+        for next in words.iter().skip(start_idx).take(3) {
             if KEYWORDS
                 .iter()
-                .filter(|k| k.0.eq(&words[i]) || k.1.eq(&words[i]))
+                .filter(|k| k.0.eq(next) || k.1.eq(next))
                 .count()
                 > 1
             {
                 return true;
             }
-            if let Ok(_) = words[i].parse::<f64>() {
+            if next.parse::<f64>().is_ok() {
                 return false;
             }
         }
@@ -411,16 +396,16 @@ pub struct Solver;
 impl Solver {
     pub fn solve_geometry_task(
         task_data: &HashMap<String, Option<RectData>>,
-        target_data: &Vec<RectTarget>,
+        target_data: &[RectTarget],
     ) -> Vec<(String, Rectangle)> {
         let mut rect = Rectangle::new(0.0, 0.0);
         let mut steps: Vec<(String, Rectangle)> = Vec::new();
 
         if let Some(RectData::SideX(width)) = task_data.get("sideX").unwrap() {
-            rect.width = *width;
+            rect.size.width = *width;
         }
         if let Some(RectData::SideY(height)) = task_data.get("sideY").unwrap() {
-            rect.height = *height;
+            rect.size.height = *height;
         }
         if let Some(RectData::Perimeter(value)) = task_data.get("perimeter").unwrap() {
             rect.traits
@@ -476,7 +461,7 @@ impl Solver {
             );
         }
 
-        let mut target_list = target_data.clone();
+        let mut target_list = target_data.to_vec();
 
         if let Some(RectData::Ratio(r1, r2)) = task_data.get("ratio").unwrap() {
             if rect.has_trait(RectTraitKey::Perimeter) {
@@ -490,18 +475,18 @@ impl Solver {
 
         steps.push(("Init".to_string(), rect.clone()));
 
-        while target_list.len() > 0 {
+        while !target_list.is_empty() {
             let mut action = "";
-            match target_list.get(0) {
+            match target_list.first() {
                 Some(RectTarget::Perimeter) => {
                     if rect.has_trait(RectTraitKey::Perimeter) {
                         target_list.remove(0);
                         action = "Perimeter is already known";
-                    } else if rect.width != 0.0 && rect.height != 0.0 {
+                    } else if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.perimeter();
                         target_list.remove(0);
                         action = "Found perimeter using sides";
-                    } else if rect.width > 0.0 || rect.height > 0.0 {
+                    } else if rect.size.width > 0.0 || rect.size.height > 0.0 {
                         if rect.has_trait(RectTraitKey::Area) {
                             rect.find_perimeter_by_area();
                             target_list.remove(0);
@@ -521,11 +506,11 @@ impl Solver {
                     if rect.has_trait(RectTraitKey::Area) {
                         target_list.remove(0);
                         action = "Area is already known";
-                    } else if rect.width != 0.0 && rect.height != 0.0 {
+                    } else if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.area();
                         target_list.remove(0);
                         action = "Found area using sides";
-                    } else if rect.width > 0.0 || rect.height > 0.0 {
+                    } else if rect.size.width > 0.0 || rect.size.height > 0.0 {
                         if rect.has_trait(RectTraitKey::Perimeter) {
                             rect.find_area_by_perimeter();
                             target_list.remove(0);
@@ -545,7 +530,7 @@ impl Solver {
                     if rect.has_trait(RectTraitKey::Diagonal) {
                         target_list.remove(0);
                         action = "Diagonal is already known";
-                    } else if rect.width != 0.0 && rect.height != 0.0 {
+                    } else if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.diagonal();
                         target_list.remove(0);
                         action = "Found diagonal using sides";
@@ -565,7 +550,7 @@ impl Solver {
                         rect.find_diagonal_by_diagonals_angle_and_area();
                         target_list.remove(0);
                         action = "Found diagonal using angle between diagonals and area";
-                    } else if rect.width > 0.0 || rect.height > 0.0 {
+                    } else if rect.size.width > 0.0 || rect.size.height > 0.0 {
                         if rect.has_trait(RectTraitKey::Perimeter) {
                             rect.find_diagonal_by_perimeter();
                             target_list.remove(0);
@@ -591,7 +576,7 @@ impl Solver {
                         target_list.remove(0);
                         action = "Found sides using side and perimeter";
                     } else if rect.has_trait(RectTraitKey::Area) {
-                        if rect.width <= 0. && rect.height <= 0. {
+                        if rect.size.width <= 0. && rect.size.height <= 0. {
                             if rect.has_trait(RectTraitKey::Diagonal) {
                                 rect.find_sides_from_area_and_diagonal();
                                 target_list.remove(0);
@@ -622,7 +607,7 @@ impl Solver {
                     }
                 }
                 Some(RectTarget::SideDistances) => {
-                    if rect.width != 0.0 && rect.height != 0.0 {
+                    if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.calculate_side_distances();
                         target_list.remove(0);
                         action = "Found distances from intersection point using sides";
@@ -632,7 +617,7 @@ impl Solver {
                 }
                 Some(RectTarget::SideXDiagonalAngle) => {
                     if rect.has_trait(RectTraitKey::Diagonal) {
-                        if rect.width != 0.0 && rect.height != 0.0 {
+                        if rect.size.width != 0.0 && rect.size.height != 0.0 {
                             rect.angle_between_side_and_diagonal();
                             target_list.remove(0);
                             action = "Found angle between diagonal and side";
@@ -659,7 +644,7 @@ impl Solver {
                 Some(RectTarget::SmallerSide) => {
                     if rect.has_trait(RectTraitKey::SmallerSide) {
                         target_list.remove(0);
-                    } else if rect.width != 0.0 && rect.height != 0.0 {
+                    } else if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.find_bigger_smaller_side();
                         target_list.remove(0);
                         action = "Found smaller side";
@@ -670,7 +655,7 @@ impl Solver {
                 Some(RectTarget::BiggerSide) => {
                     if rect.has_trait(RectTraitKey::BiggerSide) {
                         target_list.remove(0);
-                    } else if rect.width != 0.0 && rect.height != 0.0 {
+                    } else if rect.size.width != 0.0 && rect.size.height != 0.0 {
                         rect.find_bigger_smaller_side();
                         target_list.remove(0);
                         action = "Found bigger side";
@@ -736,7 +721,7 @@ impl Solver {
                 }
                 _ => {}
             }
-            if target_list.len() == 0 && (rect.width == 0.0 || rect.height == 0.0) {
+            if target_list.is_empty() && (rect.size.width == 0.0 || rect.size.height == 0.0) {
                 target_list.insert(0, RectTarget::Sides);
             }
 
@@ -1,5 +1,4 @@
 pub mod rectangle;
 pub mod udpipe_api;
 pub mod text_preprocessor;
-pub mod task_reader;
 pub mod geometry_analyser;
\ No newline at end of file
@@ -1,6 +1,5 @@
-use libm::{atan2, cos, fmax, fmin, pow, sin, sqrt, sqrtf};
-use std::any::Any;
-use std::cmp::max;
+use geometry_core::{Dimensions, Size};
+use libm::{atan2, cos, fmax, fmin, pow, sin, sqrt};
 use std::collections::HashMap;
 use serde_json::{json, Value};
 
@@ -32,15 +31,13 @@ pub enum RectTraitValue {
 
 #[derive(Debug, Clone)]
 pub struct Rectangle {
-    pub width: f64,
-    pub height: f64,
+    pub size: Size,
     pub traits: HashMap<RectTraitKey, RectTraitValue>,
 }
 impl Rectangle {
     pub fn new(width: f64, height: f64) -> Self {
         Rectangle {
-            width,
-            height,
+            size: Size::new(width, height),
             traits: HashMap::new(),
         }
     }
@@ -54,21 +51,21 @@ impl Rectangle {
     }
 
     pub fn perimeter(&mut self) -> f64 {
-        let value = 2.0 * (self.width + self.height);
+        let value = Dimensions::perimeter(self);
         self.traits
             .insert(RectTraitKey::Perimeter, RectTraitValue::Single(value));
         value
     }
 
     pub fn area(&mut self) -> f64 {
-        let value = self.width * self.height;
+        let value = Dimensions::area(self);
         self.traits
             .insert(RectTraitKey::Area, RectTraitValue::Single(value));
         value
     }
 
     pub fn diagonal(&mut self) -> f64 {
-        let value = (self.width.powi(2) + self.height.powi(2)).sqrt();
+        let value = Dimensions::diagonal(self);
         self.traits
             .insert(RectTraitKey::Diagonal, RectTraitValue::Single(value));
         value
@@ -76,13 +73,13 @@ impl Rectangle {
 
     pub fn find_diagonal_by_area(&mut self) {
         if let Some(RectTraitValue::Single(s)) = self.traits.get(&RectTraitKey::Area) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let d = (sqrt(pow(*s, 2.) + pow(side, 4.))) / side;
             self.traits
@@ -92,13 +89,13 @@ impl Rectangle {
 
     pub fn find_diagonal_by_perimeter(&mut self) {
         if let Some(RectTraitValue::Single(p)) = self.traits.get(&RectTraitKey::Perimeter) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let d = (sqrt(pow(*p, 2.) - (4. * p * side) + 8. * pow(side, 2.))) / 2.;
             self.traits
@@ -108,13 +105,13 @@ impl Rectangle {
 
     pub fn find_perimeter_by_area(&mut self) {
         if let Some(RectTraitValue::Single(s)) = self.traits.get(&RectTraitKey::Area) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let p = (2. * s + 2. * pow(side, 2.)) / side;
             self.traits
@@ -124,13 +121,13 @@ impl Rectangle {
 
     pub fn find_perimeter_by_diagonal(&mut self) {
         if let Some(RectTraitValue::Single(d)) = self.traits.get(&RectTraitKey::Diagonal) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let p = 2. * (side + sqrt(pow(*d, 2.) - pow(side, 2.)));
             self.traits
@@ -140,13 +137,13 @@ impl Rectangle {
 
     pub fn find_area_by_perimeter(&mut self) {
         if let Some(RectTraitValue::Single(p)) = self.traits.get(&RectTraitKey::Perimeter) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let s = (p * side - (2. * pow(side, 2.))) / 2.;
             self.traits
@@ -156,13 +153,13 @@ impl Rectangle {
 
     pub fn find_area_by_diagonal(&mut self) {
         if let Some(RectTraitValue::Single(d)) = self.traits.get(&RectTraitKey::Diagonal) {
-            if self.width <= 0.0 && self.height <= 0.0 {
+            if self.size.width <= 0.0 && self.size.height <= 0.0 {
                 return;
             }
-            let side = if self.width > 0.0 {
-                self.width
+            let side = if self.size.width > 0.0 {
+                self.size.width
             } else {
-                self.height
+                self.size.height
             };
             let s = side * sqrt(pow(*d, 2.) - pow(side, 2.));
             self.traits
@@ -175,39 +172,39 @@ impl Rectangle {
             Some(RectTraitValue::Single(p)) => *p,
             _ => return,
         };
-        if self.width > 0.0 && self.height > 0.0 {
+        if self.size.width > 0.0 && self.size.height > 0.0 {
             return;
         }
-        if self.width > 0.0 {
-            self.height = (p - 2. * self.width) / 2.;
+        if self.size.width > 0.0 {
+            self.size.height = (p - 2. * self.size.width) / 2.;
         }
-        if self.height > 0.0 {
-            self.width = (p - 2. * self.height) / 2.;
+        if self.size.height > 0.0 {
+            self.size.width = (p - 2. * self.size.height) / 2.;
         }
     }
     pub fn find_side_by_area(&mut self) {
         if let Some(RectTraitValue::Single(s)) = self.traits.get(&RectTraitKey::Area) {
-            if self.width > 0.0 && self.height > 0.0 {
+            if self.size.width > 0.0 && self.size.height > 0.0 {
                 return;
             }
-            if self.width > 0.0 {
-                self.height = s / self.width;
+            if self.size.width > 0.0 {
+                self.size.height = s / self.size.width;
             }
-            if self.height > 0.0 {
-                self.width = s / self.height;
+            if self.size.height > 0.0 {
+                self.size.width = s / self.size.height;
             }
         }
     }
     pub fn find_side_by_diagonal(&mut self) {
         if let Some(RectTraitValue::Single(d)) = self.traits.get(&RectTraitKey::Diagonal) {
-            if self.width > 0.0 && self.height > 0.0 {
+            if self.size.width > 0.0 && self.size.height > 0.0 {
                 return;
             }
-            if self.width > 0.0 {
-                self.height = sqrt(pow(*d, 2.) - pow(self.width, 2.));
+            if self.size.width > 0.0 {
+                self.size.height = sqrt(pow(*d, 2.) - pow(self.size.width, 2.));
             }
-            if self.height > 0.0 {
-                self.width = sqrt(pow(*d, 2.) - pow(self.height, 2.));
+            if self.size.height > 0.0 {
+                self.size.width = sqrt(pow(*d, 2.) - pow(self.size.height, 2.));
             }
         }
     }
@@ -232,8 +229,8 @@ impl Rectangle {
             let width = ((-b + discriminant.sqrt()) / (2.0 * a)).sqrt();
             let height = area / width;
 
-            self.width = width;
-            self.height = height;
+            self.size.width = width;
+            self.size.height = height;
         }
     }
     pub fn find_diagonal_by_angle_and_side(&mut self) {
@@ -267,23 +264,23 @@ impl Rectangle {
     }
 
     pub fn calculate_side_distances(&mut self) {
-        let d1 = self.width / 2.0;
-        let d2 = self.height / 2.0;
+        let d1 = self.size.width / 2.0;
+        let d2 = self.size.height / 2.0;
         self.traits
             .insert(RectTraitKey::SideDistances, RectTraitValue::Pair(d1, d2));
     }
 
     pub fn find_sides_by_side_distances(&mut self) {
         if let Some(RectTraitValue::Pair(d1, d2)) = self.traits.get(&RectTraitKey::SideDistances) {
-            self.width = d1 * 2.0;
-            self.height = d2 * 2.0;
+            self.size.width = d1 * 2.0;
+            self.size.height = d2 * 2.0;
         }
     }
 
     pub fn angle_between_side_and_diagonal(&mut self) {
         if let Some(RectTraitValue::Single(d)) = self.traits.get(&RectTraitKey::Diagonal) {
-            let angle_x = (self.width / d).asin().to_degrees();
-            let angle_y = (self.height / d).asin().to_degrees();
+            let angle_x = (self.size.width / d).asin().to_degrees();
+            let angle_y = (self.size.height / d).asin().to_degrees();
 
             self.traits.insert(
                 RectTraitKey::SideXDiagonalAngle,
@@ -312,16 +309,16 @@ impl Rectangle {
     pub fn find_sides_from_diagonal_and_ratio(&mut self, ratio: (f64, f64)) {
         let scale = (ratio.0.powi(2) + ratio.1.powi(2)).sqrt();
         if let Some(RectTraitValue::Single(d)) = self.traits.get(&RectTraitKey::Diagonal) {
-            self.width = d * (ratio.0 / scale);
-            self.height = d * (ratio.1 / scale);
+            self.size.width = d * (ratio.0 / scale);
+            self.size.height = d * (ratio.1 / scale);
         }
     }
 
     pub fn find_sides_from_perimeter_and_ratio(&mut self, ratio: (f64, f64)) {
         let scale = ratio.0 + ratio.1;
         if let Some(RectTraitValue::Single(p)) = self.traits.get(&RectTraitKey::Perimeter) {
-            self.width = p * (ratio.0 / scale) / 2.0;
-            self.height = p * (ratio.1 / scale) / 2.0;
+            self.size.width = p * (ratio.0 / scale) / 2.0;
+            self.size.height = p * (ratio.1 / scale) / 2.0;
         }
     }
 
@@ -333,17 +330,17 @@ impl Rectangle {
         if let Some(RectTraitValue::Single(x)) = self.traits.get(&RectTraitKey::SmallerSide) {
             fraction_value = x / fmin(ratio.0, ratio.1);
         }
-        self.width = ratio.0 * fraction_value;
-        self.height = ratio.1 * fraction_value;
+        self.size.width = ratio.0 * fraction_value;
+        self.size.height = ratio.1 * fraction_value;
     }
     pub fn find_bigger_smaller_side(&mut self) {
         self.traits.insert(
             RectTraitKey::SmallerSide,
-            RectTraitValue::Single(fmin(self.width, self.height)),
+            RectTraitValue::Single(self.smaller_side()),
         );
         self.traits.insert(
             RectTraitKey::BiggerSide,
-            RectTraitValue::Single(fmax(self.width, self.height)),
+            RectTraitValue::Single(self.bigger_side()),
         );
     }
 
@@ -464,9 +461,19 @@ impl Rectangle {
         }
 
         json!({
-            "width": self.width,
-            "height": self.height,
+            "width": self.size.width,
+            "height": self.size.height,
             "traits": traits_map
         })
     }
 }
+
+impl Dimensions for Rectangle {
+    fn width(&self) -> f64 {
+        self.size.width
+    }
+
+    fn height(&self) -> f64 {
+        self.size.height
+    }
+}
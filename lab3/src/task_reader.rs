@@ -1,2 +0,0 @@
-use calamine::{open_workbook, Reader, Xlsx};
-use std::error::Error;
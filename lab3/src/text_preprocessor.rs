@@ -1,5 +1,4 @@
 use regex::Regex;
-use std::collections::HashMap;
 
 pub fn preprocess(input: &str) -> String {
     let mut units = vec![
@@ -13,7 +12,7 @@ pub fn preprocess(input: &str) -> String {
         ("м", (100.0, "см")),
     ];
 
-    units.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+    units.sort_by_key(|unit| std::cmp::Reverse(unit.0.len()));
 
     let mut result = input.to_lowercase();
 
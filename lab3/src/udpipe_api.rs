@@ -6,7 +6,7 @@ const BASE_URL: &str = "https://lindat.mff.cuni.cz/services/udpipe/api/";
 pub async fn process_text(text: &str, model: &str) -> Result<Value, Box<dyn std::error::Error>> {
     let client = Client::new();
     let response = client
-        .post(&format!("{}/process", BASE_URL))
+        .post(format!("{}/process", BASE_URL))
         .form(&[("data", text), ("model", model), ("tokenizer", ""), ("tagger", ""), ("parser", "")])
         .send().await?;
     let json: Value = response.json().await?;
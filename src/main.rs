@@ -1,347 +1,117 @@
-use regex::Regex;
+use clap::{Args, Parser, Subcommand};
+use geometry_core::DEFAULT_EPSILON;
+use lab3::geometry_analyser::{GeometryTaskAnalyser, Solver};
+use lab3::text_preprocessor::{preprocess, restore_dots};
+use lab3::udpipe_api::{extract_significant_words, process_text};
 use std::error::Error;
-use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use crate::TokenType::{Arithmetic, Assignment, Comparison, Delimiter, Identifier, Invalid, Logical, StringLiteral, CharLiteral};
-
-const KEY_WORDS: [&str; 13] = ["if", "else", "let", "for", "loop", "while", "mut", "enum", "struct", "fn", "impl", "use", "macro"];
-const IDENTIFIER_SYMBOLS: &str = "^[shvachkaSHVACHKAшвачкаШВАЧКА0-9_]+$";
-const FUNCTIONS: [&str; 6] = ["sin", "cos", "tan", "ctan", "sqrt", "new"];
-const COMPARISON_SYMBOLS: [&str; 6] = ["<", "<=", ">=", ">", "==", "!="];
-const ARITHMETIC_SYMBOLS: [&str; 5] = ["-", "+", "*", "/", "%"];
-const DELIMITER_SYMBOLS: [&str; 14] = [";", ":", "::", ",", "[", "]", "(", ")", "{", "}", "?", "\'", "\"", "&"];
-const LOGICAL_SYMBOLS: [&str; 3] = ["&&", "||", "!"];
-const ASSIGNMENT_SYMBOLS: [&str; 6] = ["=", "/=", "*=", "+=", "-=", "%="];
-const DATA_TYPES: [&str; 18] = ["char", "String", "str", "bool", "f32", "f64", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
-
-const STRING_START: &str = "\"";
-const CHAR_START: &str = "'";
-
-#[derive(PartialEq, Eq)]
-#[derive(Debug)]
-enum TokenType {
-    DataType,
-    Integer,
-    Double,
-    Identifier,
-    Arithmetic,
-    Function,
-    KeyWord,
-    Delimiter,
-    Comparison,
-    Logical,
-    Assignment,
-    Boolean,
-    StringLiteral,
-    CharLiteral,
-    Invalid,
-}
-
-impl fmt::Display for TokenType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-struct Token {
-    type_name: TokenType,
-    content: String,
-}
-
-fn read_file(file_path: &str) -> Result<String, Box<dyn Error>> {
-    let input = File::open(file_path)?;
-    let buffered = BufReader::new(input);
-    let text = buffered.lines().collect::<Result<Vec<_>, _>>()?.join("");
-
-    Ok(text)
-}
-
-fn escape_special_chars(input: &str) -> String {
-    let mut escaped = String::new();
-    for c in input.chars() {
-        if r"\^$.*+?()[]{}|".contains(c) {
-            escaped.push('\\');
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+#[derive(Parser, Debug)]
+#[command(name = "systemne_labs", about = "Lexer, rectangle DSL and geometry task solver from the systems programming labs")]
+struct Cli {
+    #[command(subcommand)]
+    command: LabCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum LabCommand {
+    /// Lex, parse or run lab1 sources
+    Lex(lab1::cli::Cli),
+    /// Execute a lab2 rectangle DSL script and plot the resulting rectangles
+    Dsl(DslArgs),
+    /// Solve a lab3 rectangle word problem step by step and plot the steps
+    Solve(PlotArgs),
+}
+
+#[derive(Args, Debug)]
+struct PlotArgs {
+    /// Input file; reads stdin when omitted or `-`
+    path: Option<PathBuf>,
+
+    /// Directory that receives rectangles.json
+    #[arg(short, long, default_value = ".")]
+    output: PathBuf,
+
+    /// Python script that plots rectangles.json
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/plot/plot_rectangles.py"))]
+    script: PathBuf,
+
+    /// Write rectangles.json without starting the plot script
+    #[arg(long)]
+    no_plot: bool,
+}
+
+#[derive(Args, Debug)]
+struct DslArgs {
+    #[command(flatten)]
+    plot: PlotArgs,
+
+    /// Relative tolerance used when comparing coordinates and side lengths
+    #[arg(long, default_value_t = DEFAULT_EPSILON)]
+    epsilon: f64,
+}
+
+fn read_input(path: Option<&Path>) -> Result<String, Box<dyn Error>> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => Ok(fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?),
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
         }
-        escaped.push(c);
     }
-    escaped
-}
-
-fn generate_regex(expr: &[&str]) -> Result<Regex, Box<dyn Error>> {
-    let escaped_expr: Vec<String> = expr.iter().map(|s| escape_special_chars(s)).collect();
-    let content = format!("^({})$", escaped_expr.join("|"));
-    //println!("{:?}", content);
-    Ok(Regex::new(&*content).unwrap())
 }
 
-fn get_string_of_char(p: &usize, input: &String) -> Result<String, Box<dyn Error>> {
-    if p < &input.chars().count() {
-        if let Some(c) = input.chars().nth(*p) {
-            return Ok(c.to_string());
-        }
+fn plot(args: &PlotArgs) -> Result<(), Box<dyn Error>> {
+    if args.no_plot {
+        return Ok(());
     }
-    Err("Index out of bounds".into())
-}
-
-fn match_token_type(symbol: &str, comparison_regex: &Regex, delimiter_regex: &Regex, logical_regex: &Regex, assign_regex: &Regex) -> TokenType {
-    if comparison_regex.is_match(symbol) {
-        Comparison
-    } else if delimiter_regex.is_match(symbol) {
-        Delimiter
-    } else if logical_regex.is_match(symbol) {
-        Logical
-    } else if assign_regex.is_match(symbol) {
-        Assignment
-    } else {
-        Invalid
+    let json = args.output.join("rectangles.json");
+    let status = match Command::new("python3").arg(&args.script).arg(&json).status() {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Command::new("python").arg(&args.script).arg(&json).status()?,
+        status => status?,
+    };
+    if !status.success() {
+        return Err(format!("plot script exited with {}", status).into());
     }
+    Ok(())
 }
 
-fn lex(file_path: &str) -> Result<(Vec<Token>, Vec<Token>), Box<dyn std::error::Error>> {
-    let input = read_file(file_path)?;
-    let mut p = 0;
-    let mut tokens: Vec<Token> = Vec::new();
-    let mut identifiers: Vec<Token> = Vec::new();
-
-    let identifier_regex = Regex::new(IDENTIFIER_SYMBOLS)?;
-    let whitespace_regex = Regex::new("\\s")?;
-    let number_regex = Regex::new("[0-9]+")?;
-    let literal_start_regex = Regex::new("[a-zA-Zа-яА-ЯіІїЇєЄґҐ]+")?;
-    let literal_regex = Regex::new("[a-zA-Zа-яА-ЯіІїЇєЄґҐ0-9_]+")?;
-    let comparison_regex = generate_regex(&COMPARISON_SYMBOLS)?;
-    let assignment_regex = generate_regex(&ASSIGNMENT_SYMBOLS)?;
-    let arithmetic_regex = generate_regex(&ARITHMETIC_SYMBOLS)?;
-    let delimiter_regex = generate_regex(&DELIMITER_SYMBOLS)?;
-    let logical_regex = generate_regex(&LOGICAL_SYMBOLS)?;
-    let function_regex = generate_regex(&FUNCTIONS)?;
-    let key_words_regex = generate_regex(&KEY_WORDS)?;
-    let data_types_regex = generate_regex(&DATA_TYPES)?;
-
-    while p < input.chars().count() {
-        let current: &str = &*get_string_of_char(&p, &input)?;
-        if whitespace_regex.is_match(current) {
-            p += 1;
-            continue;
-        }
-        if current == STRING_START {
-            let token = get_string_literal_token(&mut p, &input)?;
-            tokens.push(token);
-            continue;
-        }
-        if current == CHAR_START {
-            let token = get_char_literal_token(&mut p, &input)?;
-            tokens.push(token);
-            continue;
-        }
-        if literal_start_regex.is_match(&current) {
-            let token = get_literal_token(&mut p, &input, &literal_regex, &key_words_regex, &identifier_regex, &function_regex, &data_types_regex)?;
-            if token.type_name == Identifier {
-                if !identifiers.iter().any(|id| id.content == token.content) {
-                    identifiers.push(Token { type_name: Identifier, content: token.content.clone() });
-                }
-            }
-            tokens.push(token);
-            continue;
-        }
-        if arithmetic_regex.is_match(current) {
-            let t = get_operator_or_numeric_token(&mut p, &input, &number_regex, &arithmetic_regex, &assignment_regex)?;
-            tokens.push(t);
-            continue;
-        }
-        if number_regex.is_match(current) {
-            let t = get_numeric_token(&mut p, &input, &number_regex)?;
-            tokens.push(t);
-            continue;
-        }
-        let token = get_complex_token(&mut p, &input, &comparison_regex, &delimiter_regex, &logical_regex, &assignment_regex)?;
-        tokens.push(token);
-    }
-
-    Ok((tokens, identifiers))
+fn dsl(args: &DslArgs) -> Result<(), Box<dyn Error>> {
+    let source = read_input(args.plot.path.as_deref())?;
+    fs::create_dir_all(&args.plot.output)?;
+    lab2::parse_with_epsilon(&source, &args.plot.output, args.epsilon)?;
+    plot(&args.plot)
 }
 
-fn get_complex_token(p: &mut usize, input: &String, comparison_regex: &Regex, delimiter_regex: &Regex, logical_regex: &Regex, assign_regex: &Regex) -> Result<Token, Box<dyn Error>> {
-    let current = get_string_of_char(p, input)?;
-    let is_not_the_end = *p + 1 < input.chars().count();
-    let next_char = if is_not_the_end { get_string_of_char(&(*p + 1), input)? } else { String::new() };
+fn solve(args: &PlotArgs) -> Result<(), Box<dyn Error>> {
+    let task = preprocess(&read_input(args.path.as_deref())?);
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let processed = runtime.block_on(process_text(&task, "ukrainian"))?;
+    let words = restore_dots(extract_significant_words(&processed));
 
-    let combined = current.clone() + &next_char;
-
-    if is_not_the_end && (comparison_regex.is_match(&combined) || delimiter_regex.is_match(&combined) || logical_regex.is_match(&combined)) {
-        *p += 2;
-        return Ok(Token { type_name: match_token_type(&combined, comparison_regex, delimiter_regex, logical_regex, assign_regex), content: combined });
-    }
-    if comparison_regex.is_match(&current) || delimiter_regex.is_match(&current) || assign_regex.is_match(&current) {
-        *p += 1;
-        return Ok(Token { type_name: match_token_type(&current, comparison_regex, delimiter_regex, logical_regex, assign_regex), content: current });
-    }
-
-    *p += 1;
-    Ok(Token { type_name: Invalid, content: current })
+    let (task_data, targets) = GeometryTaskAnalyser::get_task_data(words);
+    let steps = Solver::solve_geometry_task(&task_data, &targets);
+    fs::create_dir_all(&args.output)?;
+    Solver::save_steps_to_json(steps, args.output.join("rectangles.json"))?;
+    plot(args)
 }
 
-fn get_string_literal_token(p: &mut usize, input: &String) -> Result<Token, Box<dyn Error>> {
-    *p += 1;
-    let mut string_content = String::from(STRING_START);
-
-    while *p < input.chars().count() {
-        let current_char = get_string_of_char(p, input)?;
-
-        if current_char == STRING_START {
-            string_content.push_str(STRING_START);
-            *p += 1;
-            return Ok(Token {
-                type_name: StringLiteral,
-                content: string_content,
-            });
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        LabCommand::Lex(lex) => return lab1::cli::execute(lex),
+        LabCommand::Dsl(args) => dsl(args),
+        LabCommand::Solve(args) => solve(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
         }
-
-        string_content.push_str(&current_char);
-        *p += 1;
-    }
-
-    //Err("Endless string".into())
-    Ok(Token { type_name: Invalid, content: string_content })
-}
-
-fn get_char_literal_token(p: &mut usize, input: &String) -> Result<Token, Box<dyn Error>> {
-    *p += 1;
-    let mut char_content = String::from(CHAR_START);
-
-    if *p+1 < input.chars().count() {
-        if get_string_of_char(&(*p), input)? != CHAR_START
-            && get_string_of_char(&(*p + 1), input)? == CHAR_START {
-            char_content.push_str(&*get_string_of_char(p, input)?);
-            char_content.push_str(CHAR_START);
-            *p += 2;
-            return Ok(Token {type_name: CharLiteral, content: char_content});
-        }
-    }
-    Ok(Token { type_name: Delimiter, content: char_content })
-}
-
-fn get_operator_or_numeric_token(p: &mut usize, input: &String, num_regex: &Regex, arith_regex: &Regex, assign_regex: &Regex) -> Result<Token, Box<dyn Error>> {
-    let prev_symbol = if *p > 0 { get_string_of_char(&(*p-1), input)? } else { String::new() };
-    let symbol = get_string_of_char(p, input)?;
-    let mut operator = symbol.clone();
-
-    if arith_regex.is_match(&symbol) {
-        let plus_or_minus = symbol == "+" || symbol == "-";
-        *p += 1;
-        if *p < input.chars().count() {
-            let current_symbol = get_string_of_char(p, input)?;
-            if plus_or_minus && num_regex.is_match(&current_symbol)
-                && !num_regex.is_match(&prev_symbol){
-                let token = get_numeric_token(p, input, num_regex)?;
-                operator.push_str(&token.content);
-                return Ok(Token { type_name: token.type_name, content: operator });
-            }
-            if assign_regex.is_match(&(operator.clone() + &current_symbol)) {
-                operator.push_str(&current_symbol);
-                *p += 1;
-                return Ok(Token { type_name: Assignment, content: operator });
-            }
-            return Ok(Token { type_name: Arithmetic, content: operator });
-        }
-    }
-
-    Ok(Token {type_name: Invalid, content: operator})
-}
-
-fn get_literal_token(p: &mut usize, input: &String, literal_regex: &Regex, key_word_regex: &Regex, identifier_regex: &Regex, function_regex: &Regex, data_type_regex: &Regex) -> Result<Token, Box<dyn Error>> {
-    let mut literal: String = String::new();
-    while *p < input.chars().count() && literal_regex.is_match(&*get_string_of_char(&p, &input)?) {
-        literal.push_str(&*get_string_of_char(&p, &input)?);
-        *p += 1;
-    }
-
-    if literal == "true" || literal == "false" {
-        return Ok(
-            Token {
-                type_name: TokenType::Boolean,
-                content: literal,
-            }
-        );
-    }
-
-    if key_word_regex.is_match(&*literal) {
-        return Ok(
-            Token {
-                type_name: TokenType::KeyWord,
-                content: literal,
-            }
-        );
-    }
-
-    if data_type_regex.is_match(&*literal) {
-        return Ok(
-            Token {
-                type_name: TokenType::DataType,
-                content: literal,
-            }
-        );
-    }
-
-    if function_regex.is_match(&*literal) {
-        return Ok(Token { type_name: TokenType::Function, content: literal });
-    }
-
-    if identifier_regex.is_match(&*literal) {
-        return Ok(
-            Token {
-                type_name: Identifier,
-                content: literal,
-            }
-        );
-    }
-
-    println!("Invalid literal token: {}", literal);
-    Ok(
-        Token {
-            type_name: Invalid,
-            content: literal,
-        }
-    )
-}
-
-fn get_numeric_token(p: &mut usize, input: &String, regex: &Regex) -> Result<Token, Box<dyn Error>> {
-    let mut is_float = false;
-    let mut number = String::new();
-    while *p < input.chars().count() && (regex.is_match(&*get_string_of_char(&p, &input)?) || &*get_string_of_char(&p, &input)? == ".") {
-        let char = get_string_of_char(&p, &input)?;
-        if &*char == "." {
-            is_float = true;
-        }
-        number.push_str(&*char);
-        *p += 1;
-    }
-
-    Ok(
-        Token {
-            type_name: if is_float {
-                TokenType::Double
-            } else { TokenType::Integer },
-            content: number,
-        }
-    )
-}
-
-
-fn main() {
-    let path = "data/test.txt";
-    match lex(path) {
-        Ok(data) => {
-            println!("Success!\n\nTokens:\n");
-
-            for token in &data.0 {
-                println!("{} ---> {}", token.content, token.type_name.to_string());
-            }
-            println!("\nIdentifiers:\n\n");
-            for token in &data.1 {
-                println!("{} ---> {}", token.content, token.type_name.to_string());
-            }
-        },
-        Err(e) => eprintln!("Error: {}", e),
     }
 }